@@ -0,0 +1,60 @@
+// Benchmarks for the rule checks, run with `cargo bench`. Article text is repeated
+// to get a Wikipedia-sized batch of sentences, as checking them is the hot path
+// of every extraction.
+#![feature(test)]
+
+extern crate common_voice_sentence_collector;
+extern crate test;
+
+use common_voice_sentence_collector::{check, load_rules, replace_strings};
+use test::Bencher;
+
+const ARTICLE: &str = "Bern is the de facto capital of Switzerland.
+With a population of about one hundred and forty thousand, Bern is the fifth most populous city in Switzerland.
+The official language of Bern is the German language, but the main spoken language is the local variant of the Alemannic Swiss German dialect.
+In 1983 the historic old town in the centre of Bern became a UNESCO World Heritage Site.
+Bern is ranked among the world's top ten cities for the best quality of life.
+The etymology of the name Bern is uncertain.
+According to the local legend, based on folk etymology, Berchtold V, Duke of Zähringen, the founder of the city of Bern, vowed to name the city after the first animal he met on the hunt, and this turned out to be a bear.
+It has long been considered likely that the city was named after the Italian city of Verona, which at the time was known as Bern in Middle High German.
+The city grew out of a fortress built on the Aare river \"to protect the land\".
+Dr. Smith (a.k.a. the mayor) opened the new bridge in May.";
+
+const SENTENCES: usize = 1_000;
+
+fn sentences() -> Vec<&'static str> {
+    ARTICLE.lines().cycle().take(SENTENCES).collect()
+}
+
+#[bench]
+fn bench_check_compiled_rules(b: &mut Bencher) {
+    let rules = load_rules("en", None).unwrap();
+    let sentences = sentences();
+    b.bytes = sentences.iter().map(|sentence| sentence.len() as u64).sum();
+
+    b.iter(|| {
+        sentences.iter().filter(|sentence| check(&rules, sentence).is_ok()).count()
+    });
+}
+
+// Compiles the rules for every sentence, which is how the checker used to work
+// before the rules were compiled once at startup. Kept to compare against.
+#[bench]
+fn bench_check_compile_per_sentence(b: &mut Bencher) {
+    let rules = load_rules("en", None).unwrap();
+    let sentences = sentences();
+    b.bytes = sentences.iter().map(|sentence| sentence.len() as u64).sum();
+
+    b.iter(|| {
+        sentences.iter().filter(|sentence| check(&rules.compile().unwrap(), sentence).is_ok()).count()
+    });
+}
+
+#[bench]
+fn bench_replace_strings(b: &mut Bencher) {
+    let rules = load_rules("en", None).unwrap();
+    let text = sentences().join(" ");
+    b.bytes = text.len() as u64;
+
+    b.iter(|| replace_strings(&rules, &text));
+}
@@ -1,7 +1,15 @@
-use clap::{Parser, Subcommand};
+use clap::{Args as ClapArgs, Parser, Subcommand, ValueEnum};
 
-use crate::extractor::extract;
-use crate::loaders::{File, Wikipedia};
+use crate::explain::explain;
+use crate::extractor::{extract, ExtractOptions};
+use crate::filter::{filter_sentences, FilterOptions};
+use crate::loaders::{File, MediaWikiDump, Wikipedia};
+use crate::output::{OutputFormat, OutputOptions};
+use crate::rule_tests::test_rules;
+use crate::rules::DEFAULT_RULES_DIR;
+use crate::rules_diff::{diff_rules, DiffOptions};
+use crate::train_segmenter::{train_segmenter, TrainOptions};
+use crate::validate::validate_rules;
 
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
@@ -14,9 +22,18 @@ struct Args {
     #[arg(short, long)]
     language: String,
 
-    /// Path to folder with files to process
+    /// Path to folder with files to process, required for all extract commands
     #[arg(short, long)]
-    dir: String,
+    dir: Option<String>,
+
+    /// Directory with the rules files, the rules shipped with the binary are used
+    /// for all files not found in it
+    #[arg(long, env = "CV_RULES_DIR", default_value = DEFAULT_RULES_DIR)]
+    rules_dir: String,
+
+    /// Write the number of rejected sentences per rule as JSON to this path
+    #[arg(long)]
+    rejection_stats: Option<String>,
 
     #[command(subcommand)]
     command: Commands,
@@ -29,35 +46,222 @@ enum Commands {
         /// path to the file containing titles to filter for
         #[arg(short, long)]
         title_filter_list: Option<String>,
+
+        #[command(flatten)]
+        extract_args: ExtractArgs,
     },
 
     /// Extract sentences from Wikisource dump extracts using WikiExtractor
-    ExtractWikisource,
+    ExtractWikisource {
+        #[command(flatten)]
+        extract_args: ExtractArgs,
+    },
 
     /// Extract sentences from files which have one sentence per line
-    ExtractFile,
+    ExtractFile {
+        #[command(flatten)]
+        extract_args: ExtractArgs,
+    },
+
+    /// Extract sentences directly from MediaWiki XML dumps (.xml or .xml.bz2)
+    ExtractDump {
+        /// path to the file containing titles to filter for
+        #[arg(short, long)]
+        title_filter_list: Option<String>,
+
+        #[command(flatten)]
+        extract_args: ExtractArgs,
+    },
+
+    /// Check the rules file of the language for errors, such as invalid regexes
+    ValidateRules,
+
+    /// Run the test sentences of the rules file of the language
+    TestRules,
+
+    /// Check sentences, one per line, and print the accepted ones
+    Check {
+        /// path to the file with the sentences, reads from stdin if not given or "-"
+        input: Option<String>,
+
+        /// print every sentence as TSV together with the verdict and the rejecting rule
+        #[arg(long)]
+        verdicts: bool,
+    },
+
+    /// Show the replacements, the segmentation and every check for a single sentence
+    Explain {
+        /// the sentence to explain, can also be a longer text
+        sentence: String,
+    },
+
+    /// Compare the sentences two rules files accept for the same input
+    DiffRules {
+        /// path to the rules file before the change
+        #[arg(long)]
+        old: String,
+
+        /// path to the rules file after the change
+        #[arg(long)]
+        new: String,
+
+        /// kind of files in the input directory
+        #[arg(long, value_enum, default_value_t = Source::Wikipedia)]
+        source: Source,
+
+        /// number of example sentences to print per category
+        #[arg(long, default_value_t = 20)]
+        samples: usize,
+
+        /// seed for picking the example sentences
+        #[arg(long)]
+        seed: Option<u64>,
+    },
+
+    /// Train a punkt model on the texts of the language, used by all following extractions
+    TrainSegmenter {
+        /// kind of files in the input directory
+        #[arg(long, value_enum, default_value_t = Source::Wikipedia)]
+        source: Source,
+
+        /// stop reading texts after this many characters
+        #[arg(long, default_value_t = 50_000_000)]
+        max_characters: usize,
+    },
+}
+
+// Input formats for the commands that don't extract, matching the extract subcommands
+#[derive(ValueEnum, Clone, Copy, Debug)]
+enum Source {
+    Wikipedia,
+    File,
+    Dump,
+}
+
+// Arguments shared by all extract subcommands
+#[derive(ClapArgs, Debug)]
+struct ExtractArgs {
+    /// path to write all sentences rejected by the rules to, together with the
+    /// rejecting rule and their source
+    #[arg(long)]
+    rejected_out: Option<String>,
+
+    /// format to print the extracted sentences in
+    #[arg(long, value_enum, default_value_t = OutputFormat::Text)]
+    output_format: OutputFormat,
+
+    /// source name written to the cv-tsv output, defaults to the name of the loader
+    #[arg(long)]
+    source_name: Option<String>,
+
+    /// sentence domain written to the cv-tsv output
+    #[arg(long, default_value_t = String::from(""))]
+    domain: String,
+
+    /// seed for the random sentence selection, makes extractions reproducible
+    #[arg(long)]
+    seed: Option<u64>,
+
+    /// number of files read and threads splitting and checking texts in parallel
+    #[arg(short, long, default_value_t = 1)]
+    jobs: usize,
+
+    /// path to a file with sentences that should not be extracted again, one per line.
+    /// Can be given multiple times
+    #[arg(long)]
+    known_sentences: Vec<String>,
+
+    /// append newly extracted sentences to the first known sentences file
+    #[arg(long, requires = "known_sentences")]
+    update_known: bool,
+}
+
+impl ExtractArgs {
+    fn apply(&self, options: ExtractOptions) -> ExtractOptions {
+        ExtractOptions {
+            rejected_out_path: self.rejected_out.clone(),
+            output: OutputOptions {
+                format: self.output_format,
+                source_name: self.source_name.clone(),
+                domain: self.domain.clone(),
+            },
+            seed: self.seed,
+            jobs: self.jobs,
+            known_sentences_paths: self.known_sentences.clone(),
+            update_known: self.update_known,
+            ..options
+        }
+    }
 }
 
 pub fn start() -> Result<(), String> {
     let args = Args::parse();
 
-    let no_check = args.no_check;
     let language = args.language;
-    let directory = args.dir;
+    match &args.command {
+        Commands::ValidateRules => return validate_rules(&language, Some(&args.rules_dir)),
+        Commands::TestRules => return test_rules(&language, Some(&args.rules_dir)),
+        Commands::Explain { sentence } => return explain(&language, Some(&args.rules_dir), sentence),
+        Commands::Check { input, verdicts } => {
+            let filter_options = FilterOptions { input_path: input.clone(), verdicts: *verdicts };
+            return filter_sentences(&language, Some(&args.rules_dir), filter_options);
+        },
+        _ => {},
+    }
+
+    let directory = args.dir.ok_or("--dir is required to extract sentences")?;
+    let rules_dir = args.rules_dir;
+    let options = ExtractOptions {
+        no_check: args.no_check,
+        rules_dir: Some(rules_dir.clone()),
+        rejection_stats_path: args.rejection_stats,
+        ..Default::default()
+    };
 
     match &args.command {
-        Commands::Extract { title_filter_list } => {
+        Commands::Extract { title_filter_list, extract_args } => {
             let wikipedia_loader = Wikipedia::new(language, directory);
-            let filter_list_value = title_filter_list.clone().unwrap_or(String::from(""));
-            extract(wikipedia_loader, no_check, filter_list_value)
+            let filter_list_path = title_filter_list.clone().unwrap_or(String::from(""));
+            extract(wikipedia_loader, extract_args.apply(ExtractOptions { filter_list_path, ..options }))
         },
-        Commands::ExtractWikisource => {
+        Commands::ExtractWikisource { extract_args } => {
             let wikipedia_loader = Wikipedia::new(language, directory);
-            extract(wikipedia_loader, no_check, String::from(""))
+            extract(wikipedia_loader, extract_args.apply(options))
         },
-        Commands::ExtractFile => {
+        Commands::ExtractFile { extract_args } => {
             let file_loader = File::new(language, directory);
-            extract(file_loader, no_check, String::from(""))
-        }
+            extract(file_loader, extract_args.apply(options))
+        },
+        Commands::ExtractDump { title_filter_list, extract_args } => {
+            let dump_loader = MediaWikiDump::new(language, directory);
+            let filter_list_path = title_filter_list.clone().unwrap_or(String::from(""));
+            extract(dump_loader, extract_args.apply(ExtractOptions { filter_list_path, ..options }))
+        },
+        Commands::DiffRules { old, new, source, samples, seed } => {
+            let diff_options = DiffOptions {
+                old_rules_path: old.clone(),
+                new_rules_path: new.clone(),
+                rules_dir: options.rules_dir,
+                samples: *samples,
+                seed: *seed,
+            };
+            match source {
+                Source::Wikipedia => diff_rules(Wikipedia::new(language, directory), diff_options),
+                Source::File => diff_rules(File::new(language, directory), diff_options),
+                Source::Dump => diff_rules(MediaWikiDump::new(language, directory), diff_options),
+            }
+        },
+        Commands::TrainSegmenter { source, max_characters } => {
+            let train_options = TrainOptions { rules_dir, max_characters: *max_characters };
+            match source {
+                Source::Wikipedia => train_segmenter(Wikipedia::new(language, directory), train_options),
+                Source::File => train_segmenter(File::new(language, directory), train_options),
+                Source::Dump => train_segmenter(MediaWikiDump::new(language, directory), train_options),
+            }
+        },
+        Commands::ValidateRules
+        | Commands::TestRules
+        | Commands::Explain { .. }
+        | Commands::Check { .. } => unreachable!(),
     }
 }
@@ -1,597 +1,877 @@
-use crate::rules::Rules;
-use toml::Value;
-use regex::Regex;
+use crate::rules::CompiledRules;
+use std::fmt;
+
+// The reason why a sentence got rejected. Each variant maps to the rule in the
+// language rules file responsible for it, and carries the offending pattern,
+// word or symbol where there is one.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Rejection {
+    MinTrimmedLength,
+    QuoteStartWithLetter,
+    MinCharacters,
+    MaxCharacters,
+    MayEndWithColon,
+    NeedsPunctuationEnd,
+    NeedsLetterStart,
+    NeedsUppercaseStart,
+    ContainsNewline,
+    ContainsNumber,
+    AllowedSymbolsRegex(char),
+    DisallowedSymbols(char),
+    BrokenWhitespace(String),
+    MinWordCount,
+    MaxWordCount,
+    DisallowedWords(String),
+    StemSeparatorRegex(String),
+    AbbreviationPatterns(String),
+    OtherPatterns(String),
+    EvenSymbols(String),
+    MatchingSymbols(String, String),
+}
+
+impl Rejection {
+    pub fn rule(&self) -> &'static str {
+        match self {
+            Rejection::MinTrimmedLength => "min_trimmed_length",
+            Rejection::QuoteStartWithLetter => "quote_start_with_letter",
+            Rejection::MinCharacters => "min_characters",
+            Rejection::MaxCharacters => "max_characters",
+            Rejection::MayEndWithColon => "may_end_with_colon",
+            Rejection::NeedsPunctuationEnd => "needs_punctuation_end",
+            Rejection::NeedsLetterStart => "needs_letter_start",
+            Rejection::NeedsUppercaseStart => "needs_uppercase_start",
+            Rejection::ContainsNewline => "contains_newline",
+            Rejection::ContainsNumber => "contains_number",
+            Rejection::AllowedSymbolsRegex(_) => "allowed_symbols_regex",
+            Rejection::DisallowedSymbols(_) => "disallowed_symbols",
+            Rejection::BrokenWhitespace(_) => "broken_whitespace",
+            Rejection::MinWordCount => "min_word_count",
+            Rejection::MaxWordCount => "max_word_count",
+            Rejection::DisallowedWords(_) => "disallowed_words",
+            Rejection::StemSeparatorRegex(_) => "stem_separator_regex",
+            Rejection::AbbreviationPatterns(_) => "abbreviation_patterns",
+            Rejection::OtherPatterns(_) => "other_patterns",
+            Rejection::EvenSymbols(_) => "even_symbols",
+            Rejection::MatchingSymbols(_, _) => "matching_symbols",
+        }
+    }
 
-fn in_limit(x: usize, min_val: usize, max_val: usize) -> bool {
-    x >= min_val && x <= max_val
+    // The offending pattern, word or symbol, if the rule has one.
+    pub fn detail(&self) -> Option<String> {
+        match self {
+            Rejection::AllowedSymbolsRegex(symbol) | Rejection::DisallowedSymbols(symbol) => {
+                Some(symbol.to_string())
+            },
+            Rejection::BrokenWhitespace(value)
+            | Rejection::DisallowedWords(value)
+            | Rejection::StemSeparatorRegex(value)
+            | Rejection::AbbreviationPatterns(value)
+            | Rejection::OtherPatterns(value)
+            | Rejection::EvenSymbols(value) => Some(value.clone()),
+            Rejection::MatchingSymbols(first, second) => Some(format!("{} {}", first, second)),
+            _ => None,
+        }
+    }
 }
 
-pub fn check(rules: &Rules, raw: &str) -> bool {
-    let trimmed = raw.trim();
-    let alpha_cnt = trimmed.chars().filter(|c| c.is_alphabetic()).count();
-    if trimmed.len() < rules.min_trimmed_length
-        || rules.quote_start_with_letter
-            && trimmed.starts_with('"')
-            && trimmed
-                .chars()
-                .nth(1)
-                .map(|c| !c.is_alphabetic())
-                .unwrap_or_default()
-        || !in_limit(alpha_cnt, rules.min_characters, rules.max_characters)
-        || !rules.may_end_with_colon && trimmed.ends_with(':')
-        || rules.needs_punctuation_end && trimmed.ends_with(|c: char| c.is_alphabetic())
-        || rules.needs_letter_start && trimmed.starts_with(|c: char| !c.is_alphabetic())
-        || rules.needs_uppercase_start && trimmed.starts_with(|c: char| c.is_lowercase())
-        || trimmed.contains('\n')
-        || trimmed.contains(char::is_numeric)
+impl fmt::Display for Rejection {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.detail() {
+            Some(detail) => write!(f, "{} ({:?})", self.rule(), detail),
+            None => write!(f, "{}", self.rule()),
+        }
+    }
+}
+
+pub type CheckResult = Result<(), Rejection>;
+
+type Check = fn(&CompiledRules, &str) -> CheckResult;
+
+// All checks in the order they run, each with the rule it belongs to. The checks
+// get the trimmed sentence.
+const CHECKS: [(&str, Check); 21] = [
+    ("min_trimmed_length", check_min_trimmed_length),
+    ("quote_start_with_letter", check_quote_start_with_letter),
+    ("min_characters", check_min_characters),
+    ("max_characters", check_max_characters),
+    ("may_end_with_colon", check_may_end_with_colon),
+    ("needs_punctuation_end", check_needs_punctuation_end),
+    ("needs_letter_start", check_needs_letter_start),
+    ("needs_uppercase_start", check_needs_uppercase_start),
+    ("contains_newline", check_contains_newline),
+    ("contains_number", check_contains_number),
+    ("allowed_symbols_regex", check_allowed_symbols_regex),
+    ("disallowed_symbols", check_disallowed_symbols),
+    ("broken_whitespace", check_broken_whitespace),
+    ("min_word_count", check_min_word_count),
+    ("max_word_count", check_max_word_count),
+    ("disallowed_words", check_disallowed_words),
+    ("stem_separator_regex", check_stem_separator_regex),
+    ("abbreviation_patterns", check_abbreviation_patterns),
+    ("other_patterns", check_other_patterns),
+    ("even_symbols", check_even_symbols),
+    ("matching_symbols", check_matching_symbols),
+];
+
+fn alpha_count(trimmed: &str) -> usize {
+    trimmed.chars().filter(|c| c.is_alphabetic()).count()
+}
+
+fn check_min_trimmed_length(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if trimmed.len() < rules.min_trimmed_length {
+        return Err(Rejection::MinTrimmedLength);
+    }
+    Ok(())
+}
+
+fn check_quote_start_with_letter(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.quote_start_with_letter
+        && trimmed.starts_with('"')
+        && trimmed
+            .chars()
+            .nth(1)
+            .map(|c| !c.is_alphabetic())
+            .unwrap_or_default()
     {
-        return false;
+        return Err(Rejection::QuoteStartWithLetter);
+    }
+    Ok(())
+}
+
+fn check_min_characters(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if alpha_count(trimmed) < rules.min_characters {
+        return Err(Rejection::MinCharacters);
     }
+    Ok(())
+}
 
-    let invalid_symbols = if !rules.allowed_symbols_regex.is_empty() {
-            let regex = Regex::new(&rules.allowed_symbols_regex).unwrap();
-            trimmed.chars().any(|c| {
-                !regex.is_match(&c.to_string())
-            })
-        } else {
-            trimmed.chars().any(|c| {
-                rules.disallowed_symbols.contains(&Value::try_from(c).unwrap())
-            })
-        };
+fn check_max_characters(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if alpha_count(trimmed) > rules.max_characters {
+        return Err(Rejection::MaxCharacters);
+    }
+    Ok(())
+}
 
-    if invalid_symbols {
-        return false;
+fn check_may_end_with_colon(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if !rules.may_end_with_colon && trimmed.ends_with(':') {
+        return Err(Rejection::MayEndWithColon);
     }
+    Ok(())
+}
 
-    if rules.broken_whitespace.iter().any(|broken| trimmed.contains(Value::as_str(broken).unwrap())) {
-        return false;
+fn check_needs_punctuation_end(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.needs_punctuation_end && trimmed.ends_with(|c: char| c.is_alphabetic()) {
+        return Err(Rejection::NeedsPunctuationEnd);
     }
+    Ok(())
+}
 
-    let words = trimmed.split_whitespace();
-    let word_count = words.clone().count();
-    if word_count < rules.min_word_count
-        || word_count > rules.max_word_count
-        || words.clone().any(|word| rules.disallowed_words.contains(
-             &word.trim_matches(|c: char| !c.is_alphabetic()).to_lowercase()
-           ))
+fn check_needs_letter_start(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.needs_letter_start && trimmed.starts_with(|c: char| !c.is_alphabetic()) {
+        return Err(Rejection::NeedsLetterStart);
+    }
+    Ok(())
+}
+
+fn check_needs_uppercase_start(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.needs_uppercase_start && trimmed.starts_with(|c: char| c.is_lowercase()) {
+        return Err(Rejection::NeedsUppercaseStart);
+    }
+    Ok(())
+}
+
+fn check_contains_newline(_rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if trimmed.contains('\n') {
+        return Err(Rejection::ContainsNewline);
+    }
+    Ok(())
+}
+
+fn check_contains_number(_rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if trimmed.contains(char::is_numeric) {
+        return Err(Rejection::ContainsNumber);
+    }
+    Ok(())
+}
+
+fn check_allowed_symbols_regex(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(regex) = &rules.allowed_symbols_regex {
+        let mut buffer = [0; 4];
+        if let Some(symbol) = trimmed.chars().find(|c| !regex.is_match(c.encode_utf8(&mut buffer))) {
+            return Err(Rejection::AllowedSymbolsRegex(symbol));
+        }
+    }
+    Ok(())
+}
+
+// Only used if there is no allowed_symbols_regex
+fn check_disallowed_symbols(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.allowed_symbols_regex.is_some() {
+        return Ok(());
+    }
+    if let Some(symbol) = trimmed.chars().find(|c| rules.disallowed_symbols.contains(c)) {
+        return Err(Rejection::DisallowedSymbols(symbol));
+    }
+    Ok(())
+}
+
+fn check_broken_whitespace(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(broken) = rules.broken_whitespace.iter().find(|broken| trimmed.contains(broken.as_str())) {
+        return Err(Rejection::BrokenWhitespace(broken.clone()));
+    }
+    Ok(())
+}
+
+fn check_min_word_count(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.words(trimmed).len() < rules.min_word_count {
+        return Err(Rejection::MinWordCount);
+    }
+    Ok(())
+}
+
+fn check_max_word_count(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if rules.words(trimmed).len() > rules.max_word_count {
+        return Err(Rejection::MaxWordCount);
+    }
+    Ok(())
+}
+
+fn check_disallowed_words(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    // Words of the dictionary tokenizer contain no punctuation, and trimming them would
+    // remove marks such as the Thai tone marks which are not alphabetic
+    let trim = rules.dictionary_tokenizer.is_none();
+    if let Some(word) = rules.words(trimmed).into_iter()
+        .map(|word| if trim { word.trim_matches(|c: char| !c.is_alphabetic()) } else { word })
+        .map(|word| word.to_lowercase())
+        .find(|word| rules.disallowed_words.contains(word))
     {
-        return false;
+        return Err(Rejection::DisallowedWords(word));
     }
+    Ok(())
+}
 
-    if !rules.stem_separator_regex.is_empty() {
-        let regex: Regex = Regex::new(&rules.stem_separator_regex).unwrap();
+fn check_stem_separator_regex(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(regex) = &rules.stem_separator_regex {
         let mut stems_words: Vec<&str> = vec![];
-        
-        for word in words {
+
+        for word in rules.words(trimmed) {
             let maybe_stem_word = regex.split(word).next().unwrap_or(word);
             if maybe_stem_word != word {
                 stems_words.push(maybe_stem_word);
             }
         }
 
-        if stems_words.into_iter().any(|word| rules.disallowed_words.contains(
-            &word.to_lowercase()
-        )) {
-            return false;
+        if let Some(word) = stems_words.into_iter()
+            .map(|word| word.to_lowercase())
+            .find(|word| rules.disallowed_words.contains(word))
+        {
+            return Err(Rejection::StemSeparatorRegex(word));
         }
     }
+    Ok(())
+}
 
-    let abbr = rules.abbreviation_patterns.iter().any(|pattern| {
-        let regex = Regex::new(Value::as_str(pattern).unwrap()).unwrap();
-        regex.is_match(trimmed)
-    });
-    let other = rules.other_patterns.iter().any(|pattern| {
-        let regex = Regex::new(Value::as_str(pattern).unwrap()).unwrap();
-        regex.is_match(trimmed)
-    });
-    if abbr || other {
-        return false;
-    }
-
-    if !rules.even_symbols.is_empty() {
-        let has_uneven_symbols = rules.even_symbols.iter().any(|even_symbol| {
-            let count = trimmed.matches(Value::as_str(even_symbol).unwrap()).count();
-            count % 2 != 0
-        });
-        if has_uneven_symbols {
-            return false;
-        }
+fn check_abbreviation_patterns(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(pattern) = rules.abbreviation_patterns.iter().find(|regex| regex.is_match(trimmed)) {
+        return Err(Rejection::AbbreviationPatterns(pattern.as_str().to_string()));
     }
+    Ok(())
+}
 
-    if !rules.matching_symbols.is_empty() {
-        let has_unmatching_symbols = rules.matching_symbols.iter().any(|match_symbol| {
-            let first_count = trimmed.matches(match_symbol[0].as_str().unwrap()).count();
-            let second_count = trimmed.matches(match_symbol[1].as_str().unwrap()).count();
-            first_count != second_count
-        });
-        if has_unmatching_symbols {
-            return false;
-        }
+fn check_other_patterns(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(pattern) = rules.other_patterns.iter().find(|regex| regex.is_match(trimmed)) {
+        return Err(Rejection::OtherPatterns(pattern.as_str().to_string()));
+    }
+    Ok(())
+}
+
+fn check_even_symbols(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some(even_symbol) = rules.even_symbols.iter().find(|even_symbol| {
+        let count = trimmed.matches(even_symbol.as_str()).count();
+        count % 2 != 0
+    }) {
+        return Err(Rejection::EvenSymbols(even_symbol.clone()));
+    }
+    Ok(())
+}
+
+fn check_matching_symbols(rules: &CompiledRules, trimmed: &str) -> CheckResult {
+    if let Some((first, second)) = rules.matching_symbols.iter().find(|(first, second)| {
+        let first_count = trimmed.matches(first.as_str()).count();
+        let second_count = trimmed.matches(second.as_str()).count();
+        first_count != second_count
+    }) {
+        return Err(Rejection::MatchingSymbols(first.clone(), second.clone()));
     }
+    Ok(())
+}
+
+// Stops at the first check the sentence fails
+pub fn check(rules: &CompiledRules, raw: &str) -> CheckResult {
+    let trimmed = raw.trim();
+    CHECKS.iter().try_for_each(|(_, check)| check(rules, trimmed))
+}
 
-    true
+// Runs every check, even after one failed, to show all problems of a sentence
+pub fn check_all(rules: &CompiledRules, raw: &str) -> Vec<(&'static str, CheckResult)> {
+    let trimmed = raw.trim();
+    CHECKS.iter().map(|(rule, check)| (*rule, check(rules, trimmed))).collect()
 }
 
 #[cfg(test)]
 mod test {
     use super::*;
-    use crate::rules::load_rules;
-    use toml::Value;
+    use crate::rules::{load_rules, Rules};
 
     #[test]
     fn test_min_trimmed_length() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             min_trimmed_length: 3,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("  aa     ")));
-        assert!(check(&rules, &String::from("  aaa     ")));
+        assert!(check(&rules, &String::from("  aa     ")).is_err());
+        assert!(check(&rules, &String::from("  aaa     ")).is_ok());
     }
 
     #[test]
     fn test_min_word_count() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             min_word_count: 2,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("one")));
-        assert!(check(&rules, &String::from("two words")));
+        assert!(check(&rules, &String::from("one")).is_err());
+        assert!(check(&rules, &String::from("two words")).is_ok());
     }
 
     #[test]
     fn test_max_word_count() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             max_word_count: 2,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("three words now")));
-        assert!(check(&rules, &String::from("two words")));
+        assert!(check(&rules, &String::from("three words now")).is_err());
+        assert!(check(&rules, &String::from("two words")).is_ok());
     }
 
     #[test]
     fn test_min_characters() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             min_characters: 3,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("no!!")));
-        assert!(check(&rules, &String::from("yes!")));
+        assert!(check(&rules, &String::from("no!!")).is_err());
+        assert!(check(&rules, &String::from("yes!")).is_ok());
     }
 
     #[test]
     fn test_max_characters() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             max_characters: 25,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This is a very long sentence which should not be accepted")));
-        assert!(check(&rules, &String::from("This is a short sentence")));
+        assert!(check(&rules, &String::from("This is a very long sentence which should not be accepted")).is_err());
+        assert!(check(&rules, &String::from("This is a short sentence")).is_ok());
     }
 
     #[test]
     fn test_may_end_with_colon() {
-        let mut rules : Rules = Rules {
+        let mut rules = Rules {
             may_end_with_colon: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("ends with colon:")));
+        assert!(check(&rules, &String::from("ends with colon:")).is_err());
 
         rules = Rules {
             may_end_with_colon: true,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("ends with colon:")));
+        assert!(check(&rules, &String::from("ends with colon:")).is_ok());
     }
 
     #[test]
     fn test_quote_start_with_letter() {
-        let mut rules : Rules = Rules {
+        let mut rules = Rules {
             quote_start_with_letter: false,
             needs_letter_start: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("\"😊 foo")));
+        assert!(check(&rules, &String::from("\"😊 foo")).is_ok());
 
         rules = Rules {
             quote_start_with_letter: true,
             needs_letter_start: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("\"😊 foo")));
+        assert!(check(&rules, &String::from("\"😊 foo")).is_err());
     }
 
     #[test]
     fn test_needs_punctuation_end() {
-        let mut rules : Rules = Rules {
+        let mut rules = Rules {
             needs_punctuation_end: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has no punctuation")));
-        assert!(check(&rules, &String::from("This has punctuation.")));
+        assert!(check(&rules, &String::from("This has no punctuation")).is_ok());
+        assert!(check(&rules, &String::from("This has punctuation.")).is_ok());
 
         rules = Rules {
             needs_punctuation_end: true,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This has no punctuation")));
-        assert!(check(&rules, &String::from("This has punctuation.")));
+        assert!(check(&rules, &String::from("This has no punctuation")).is_err());
+        assert!(check(&rules, &String::from("This has punctuation.")).is_ok());
     }
 
     #[test]
     fn test_needs_letter_start() {
-        let mut rules : Rules = Rules {
+        let mut rules = Rules {
             needs_letter_start: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("?Foo")));
-        assert!(check(&rules, &String::from("This has a normal start")));
+        assert!(check(&rules, &String::from("?Foo")).is_ok());
+        assert!(check(&rules, &String::from("This has a normal start")).is_ok());
 
         rules = Rules {
             needs_letter_start: true,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("?Foo")));
-        assert!(check(&rules, &String::from("This has a normal start")));
+        assert!(check(&rules, &String::from("?Foo")).is_err());
+        assert!(check(&rules, &String::from("This has a normal start")).is_ok());
     }
 
     #[test]
     fn test_needs_uppercase_start() {
-        let mut rules : Rules = Rules {
+        let mut rules = Rules {
             needs_uppercase_start: false,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("foo")));
-        assert!(check(&rules, &String::from("Foo")));
+        assert!(check(&rules, &String::from("foo")).is_ok());
+        assert!(check(&rules, &String::from("Foo")).is_ok());
 
         rules = Rules {
             needs_uppercase_start: true,
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("foo")));
-        assert!(check(&rules, &String::from("Foo")));
+        assert!(check(&rules, &String::from("foo")).is_err());
+        assert!(check(&rules, &String::from("Foo")).is_ok());
     }
 
     #[test]
     fn test_disallowed_symbols() {
-        let rules : Rules = Rules {
-            disallowed_symbols: vec![Value::try_from('%').unwrap()],
+        let rules = Rules {
+            disallowed_symbols: vec![String::from("%")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has no percentage but other & characters")));
-        assert!(!check(&rules, &String::from("This has a %")));
+        assert!(check(&rules, &String::from("This has no percentage but other & characters")).is_ok());
+        assert!(check(&rules, &String::from("This has a %")).is_err());
     }
 
     #[test]
     fn test_allowed_symbols_regex() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             allowed_symbols_regex: String::from("[\u{0020}-\u{005A}]"),
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("ONLY UPPERCASE AND SPACE IS ALLOWED")));
-        assert!(!check(&rules, &String::from("This is not uppercase")));
+        assert!(check(&rules, &String::from("ONLY UPPERCASE AND SPACE IS ALLOWED")).is_ok());
+        assert!(check(&rules, &String::from("This is not uppercase")).is_err());
     }
 
     #[test]
     fn test_allowed_symbols_regex_over_disallowed() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             allowed_symbols_regex: String::from("[\u{0020}-\u{005A}]"),
-            disallowed_symbols: vec![Value::try_from('O').unwrap()],
+            disallowed_symbols: vec![String::from("O")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("ONLY UPPERCASE AND SPACE IS ALLOWED AND DISALLOWED O IS OKAY")));
+        assert!(check(&rules, &String::from("ONLY UPPERCASE AND SPACE IS ALLOWED AND DISALLOWED O IS OKAY")).is_ok());
     }
 
     #[test]
     fn test_disallowed_words() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             disallowed_words: ["blerg"].iter().map(|s| (*s).to_string()).collect(),
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This has blerg")));
-        assert!(!check(&rules, &String::from("This has a capital bLeRg")));
-        assert!(!check(&rules, &String::from("This has many blergs blerg blerg blerg")));
-        assert!(!check(&rules, &String::from("Here is a blerg, with comma")));
-        assert!(check(&rules, &String::from("This hasn't bl e r g")));
+        assert!(check(&rules, &String::from("This has blerg")).is_err());
+        assert!(check(&rules, &String::from("This has a capital bLeRg")).is_err());
+        assert!(check(&rules, &String::from("This has many blergs blerg blerg blerg")).is_err());
+        assert!(check(&rules, &String::from("Here is a blerg, with comma")).is_err());
+        assert!(check(&rules, &String::from("This hasn't bl e r g")).is_ok());
 
-        let rules : Rules = Rules {
+        let rules = Rules {
             disallowed_words: ["a's"].iter().map(|s| (*s).to_string()).collect(),
             ..Default::default()
-        };
-        assert!(!check(&rules, &String::from("This has a's")));
+        }.compile().unwrap();
+        assert!(check(&rules, &String::from("This has a's")).is_err());
     }
 
     #[test]
     fn test_stem_separator_regex() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             stem_separator_regex: "[']".to_string(),
             disallowed_words: ["Smithsonian", "DC", "Museum"].iter().map(|s| (*s).to_string().to_lowercase()).collect(),
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("The Mall has many museums.")));
-        assert!(!check(&rules, &String::from("Smithsonian's venues are in the Mall.")));
-        assert!(!check(&rules, &String::from("Do you know Smithsonian's African American Museum's location?")));
-        assert!(!check(&rules, &String::from("Washington DC's Mall has many museums.")));
+        assert!(check(&rules, &String::from("The Mall has many museums.")).is_ok());
+        assert!(check(&rules, &String::from("Smithsonian's venues are in the Mall.")).is_err());
+        assert!(check(&rules, &String::from("Do you know Smithsonian's African American Museum's location?")).is_err());
+        assert!(check(&rules, &String::from("Washington DC's Mall has many museums.")).is_err());
 
-        let rules : Rules = Rules {
+        let rules = Rules {
             disallowed_words: ["Smithsonian", "DC", "Museum"].iter().map(|s| (*s).to_string()).collect(),
             ..Default::default()
-        };
-        assert!(check(&rules, &String::from("Smithsonian's venues are in DC's Mall - no check for stems.")));
+        }.compile().unwrap();
+        assert!(check(&rules, &String::from("Smithsonian's venues are in DC's Mall - no check for stems.")).is_ok());
     }
 
     #[test]
     fn test_broken_whitespace() {
-        let rules : Rules = Rules {
-            broken_whitespace: vec![Value::try_from("  ").unwrap()],
+        let rules = Rules {
+            broken_whitespace: vec![String::from("  ")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has no broken whitespace")));
-        assert!(!check(&rules, &String::from("This has  broken whitespace")));
+        assert!(check(&rules, &String::from("This has no broken whitespace")).is_ok());
+        assert!(check(&rules, &String::from("This has  broken whitespace")).is_err());
     }
 
     #[test]
     fn test_abbreviation_patterns() {
-        let rules : Rules = Rules {
-            abbreviation_patterns: vec![Value::try_from("[A-Z]{2}").unwrap()],
+        let rules = Rules {
+            abbreviation_patterns: vec![String::from("[A-Z]{2}")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This no two following uppercase letters")));
-        assert!(!check(&rules, &String::from("This has two FOllowing uppercase letters")));
+        assert!(check(&rules, &String::from("This no two following uppercase letters")).is_ok());
+        assert!(check(&rules, &String::from("This has two FOllowing uppercase letters")).is_err());
     }
 
     #[test]
     fn test_other_patterns_long_words() {
-        let rules : Rules = Rules {
-            other_patterns: vec![Value::try_from("\\w{5,50}").unwrap()],
+        let rules = Rules {
+            other_patterns: vec![String::from("\\w{5,50}")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("ये कलाकृतियां खजुराहो मंदिर की कलाकृतियों की याद दिलाती हैं.")));
-        assert!(!check(&rules, &String::from("φφδφξασκ")));
-        assert!(check(&rules, &String::from("No long test")));
-        assert!(!check(&rules, &String::from("Longlong test this is")));
-        assert!(!check(&rules, &String::from("This is longlong test")));
-        assert!(!check(&rules, &String::from("This is test which is longlong")));
+        assert!(check(&rules, &String::from("ये कलाकृतियां खजुराहो मंदिर की कलाकृतियों की याद दिलाती हैं.")).is_err());
+        assert!(check(&rules, &String::from("φφδφξασκ")).is_err());
+        assert!(check(&rules, &String::from("No long test")).is_ok());
+        assert!(check(&rules, &String::from("Longlong test this is")).is_err());
+        assert!(check(&rules, &String::from("This is longlong test")).is_err());
+        assert!(check(&rules, &String::from("This is test which is longlong")).is_err());
     }
 
     #[test]
     fn test_uneven_quotes_allowed_default() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has \"uneven quotes and it is fine!")));
+        assert!(check(&rules, &String::from("This has \"uneven quotes and it is fine!")).is_ok());
     }
 
     #[test]
     fn test_uneven_quotes_allowed() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             even_symbols: vec![],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has \"uneven quotes and it is fine!")));
-        assert!(check(&rules, &String::from("This has (uneven parenthesis and it is fine!")));
+        assert!(check(&rules, &String::from("This has \"uneven quotes and it is fine!")).is_ok());
+        assert!(check(&rules, &String::from("This has (uneven parenthesis and it is fine!")).is_ok());
     }
 
     #[test]
     fn test_uneven_quotes_not_allowed() {
-        let rules : Rules = Rules {
-            even_symbols: vec![Value::try_from("\"").unwrap(), Value::try_from("(").unwrap()],
+        let rules = Rules {
+            even_symbols: vec![String::from("\""), String::from("(")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This has \"uneven quotes and it is not fine!")));
-        assert!(!check(&rules, &String::from("This has (uneven parenthesis and it is not fine!")));
+        assert!(check(&rules, &String::from("This has \"uneven quotes and it is not fine!")).is_err());
+        assert!(check(&rules, &String::from("This has (uneven parenthesis and it is not fine!")).is_err());
     }
 
     #[test]
     fn test_uneven_quotes_not_allowed_even() {
-        let rules : Rules = Rules {
-            even_symbols: vec![Value::try_from("\"").unwrap()],
+        let rules = Rules {
+            even_symbols: vec![String::from("\"")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This has \"even\" quotes and it is fine!")));
+        assert!(check(&rules, &String::from("This has \"even\" quotes and it is fine!")).is_ok());
     }
 
     #[test]
     fn test_uneven_quotes_not_allowed_multiple() {
-        let rules : Rules = Rules {
-            even_symbols: vec![Value::try_from("\"").unwrap(), Value::try_from("'").unwrap()],
+        let rules = Rules {
+            even_symbols: vec![String::from("\""), String::from("'")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This has \"uneven quotes' and it is fine!")));
+        assert!(check(&rules, &String::from("This has \"uneven quotes' and it is fine!")).is_err());
     }
 
     #[test]
     fn test_uneven_quotes_not_allowed_multiple_one_ok() {
-        let rules : Rules = Rules {
-            even_symbols: vec![Value::try_from("\"").unwrap(), Value::try_from("'").unwrap()],
+        let rules = Rules {
+            even_symbols: vec![String::from("\""), String::from("'")],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This has \"uneven\" quotes' and it is fine!")));
+        assert!(check(&rules, &String::from("This has \"uneven\" quotes' and it is fine!")).is_err());
     }
 
     #[test]
     fn test_matching_quotes_valid() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("„").unwrap(), Value::try_from("“").unwrap()]).unwrap()
+                (String::from("„"), String::from("“"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This is „a quote“")));
+        assert!(check(&rules, &String::from("This is „a quote“")).is_ok());
     }
 
     #[test]
     fn test_matching_quotes_invalid() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("„").unwrap(), Value::try_from("“").unwrap()]).unwrap()
+                (String::from("„"), String::from("“"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This is „a quote")));
+        assert!(check(&rules, &String::from("This is „a quote")).is_err());
     }
 
     #[test]
     fn test_matching_quotes_valid_multiple() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("„").unwrap(), Value::try_from("“").unwrap()]).unwrap()
+                (String::from("„"), String::from("“"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This is „a quote“ and „another one“")));
+        assert!(check(&rules, &String::from("This is „a quote“ and „another one“")).is_ok());
     }
 
     #[test]
     fn test_matching_quotes_invalid_multiple() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("„").unwrap(), Value::try_from("“").unwrap()]).unwrap()
+                (String::from("„"), String::from("“"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This is „a quote“ and another one“")));
+        assert!(check(&rules, &String::from("This is „a quote“ and another one“")).is_err());
     }
 
     #[test]
     fn test_matching_bracket_valid() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("(").unwrap(), Value::try_from("]").unwrap()]).unwrap()
+                (String::from("("), String::from("]"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
 
-        assert!(check(&rules, &String::from("This is (a bracket]")));
+        assert!(check(&rules, &String::from("This is (a bracket]")).is_ok());
     }
 
     #[test]
     fn test_matching_bracket_invalid() {
-        let rules : Rules = Rules {
+        let rules = Rules {
             matching_symbols: vec![
-                Value::try_from([Value::try_from("(").unwrap(), Value::try_from("]").unwrap()]).unwrap()
+                (String::from("("), String::from("]"))
             ],
             ..Default::default()
-        };
+        }.compile().unwrap();
+
+        assert!(check(&rules, &String::from("This is (a bracket")).is_err());
+    }
+
+    #[test]
+    fn test_rejection_reasons() {
+        let rules = Rules {
+            max_word_count: 3,
+            disallowed_words: ["blerg"].iter().map(|s| (*s).to_string()).collect(),
+            other_patterns: vec![String::from("foo")],
+            ..Default::default()
+        }.compile().unwrap();
+
+        assert_eq!(check(&rules, "This is a long sentence"), Err(Rejection::MaxWordCount));
+        assert_eq!(check(&rules, "This has blerg"), Err(Rejection::DisallowedWords(String::from("blerg"))));
+        assert_eq!(check(&rules, "This has foo"), Err(Rejection::OtherPatterns(String::from("foo"))));
+        assert_eq!(check(&rules, "This has 1"), Err(Rejection::ContainsNumber));
+        assert_eq!(check(&rules, "This is fine"), Ok(()));
+    }
+
+    #[test]
+    fn test_rejection_display() {
+        assert_eq!(Rejection::MaxWordCount.to_string(), "max_word_count");
+        assert_eq!(Rejection::DisallowedSymbols('%').to_string(), "disallowed_symbols (\"%\")");
+        assert_eq!(Rejection::MatchingSymbols(String::from("("), String::from(")")).rule(), "matching_symbols");
+    }
+
+    #[test]
+    fn test_check_all() {
+        let rules = Rules {
+            max_word_count: 3,
+            other_patterns: vec![String::from("foo")],
+            ..Default::default()
+        }.compile().unwrap();
+
+        let results = check_all(&rules, "This has foo and 1");
+        assert_eq!(results.len(), CHECKS.len());
+        let failed = results.into_iter().filter(|(_, result)| result.is_err()).collect::<Vec<_>>();
+        assert_eq!(failed, vec![
+            ("contains_number", Err(Rejection::ContainsNumber)),
+            ("max_word_count", Err(Rejection::MaxWordCount)),
+            ("other_patterns", Err(Rejection::OtherPatterns(String::from("foo")))),
+        ]);
+    }
+
+    #[test]
+    fn test_dictionary_words() {
+        let rules = Rules {
+            word_tokenizer: String::from("dictionary"),
+            dictionary_words: ["我", "喜欢", "北京", "大学", "ไม่", "ดี"].iter().map(|word| word.to_string()).collect(),
+            disallowed_words: ["大学", "ไม่"].iter().map(|word| word.to_string()).collect(),
+            min_trimmed_length: 0,
+            min_word_count: 2,
+            max_word_count: 3,
+            ..Default::default()
+        }.compile().unwrap();
+
+        assert_eq!(check(&rules, "我喜欢北京。"), Ok(()));
+        assert_eq!(check(&rules, "北京"), Err(Rejection::MinWordCount));
+        assert_eq!(check(&rules, "我喜欢北京北京"), Err(Rejection::MaxWordCount));
+        assert_eq!(check(&rules, "北京大学"), Err(Rejection::DisallowedWords(String::from("大学"))));
+        assert_eq!(check(&rules, "ไม่ดี"), Err(Rejection::DisallowedWords(String::from("ไม่"))));
+    }
+
+    // Every check needs to be listed with the rule its rejections belong to
+    #[test]
+    fn test_check_rules() {
+        let rules = Rules {
+            min_trimmed_length: 100,
+            ..Default::default()
+        }.compile().unwrap();
 
-        assert!(!check(&rules, &String::from("This is (a bracket")));
+        for (rule, result) in check_all(&rules, "x") {
+            if let Err(rejection) = result {
+                assert_eq!(rejection.rule(), rule);
+            }
+        }
     }
 
     #[test]
     fn test_english() {
-        let rules : Rules = load_rules("en");
-
-        assert!(check(&rules, &String::from("This is absolutely valid.")));
-        assert!(!check(&rules, &String::from("this is lowercase")));
-        assert!(!check(&rules, ""));
-        assert!(!check(&rules, &String::from("\"😊")));
-        assert!(!check(&rules, &String::from("This ends with:")));
-        assert!(!check(&rules, &String::from(" AA ")));
-        assert!(!check(&rules, &String::from("This has broken  space")));
-        assert!(!check(&rules, &String::from("This as well !")));
-        assert!(!check(&rules, &String::from("And this ;")));
-        assert!(!check(&rules, &String::from("This is gonna be way way way way way way way way way way too long")));
-        assert!(!check(&rules, &String::from("This contains 1 number")));
-        assert!(!check(&rules, &String::from("foo\n\nfoo")));
-        assert!(!check(&rules, &String::from("foo\\foo")));
-        assert!(!check(&rules, &String::from("foo<>")));
-        assert!(!check(&rules, &String::from("foo*@")));
-        assert!(!check(&rules, &String::from("A.B")));
-        assert!(!check(&rules, &String::from("S.T.A.L.K.E.R.")));
+        let rules = load_rules("en", None).unwrap();
+
+        assert!(check(&rules, &String::from("This is absolutely valid.")).is_ok());
+        assert!(check(&rules, &String::from("this is lowercase")).is_err());
+        assert!(check(&rules, "").is_err());
+        assert!(check(&rules, &String::from("\"😊")).is_err());
+        assert!(check(&rules, &String::from("This ends with:")).is_err());
+        assert!(check(&rules, &String::from(" AA ")).is_err());
+        assert!(check(&rules, &String::from("This has broken  space")).is_err());
+        assert!(check(&rules, &String::from("This as well !")).is_err());
+        assert!(check(&rules, &String::from("And this ;")).is_err());
+        assert!(check(&rules, &String::from("This is gonna be way way way way way way way way way way too long")).is_err());
+        assert!(check(&rules, &String::from("This contains 1 number")).is_err());
+        assert!(check(&rules, &String::from("foo\n\nfoo")).is_err());
+        assert!(check(&rules, &String::from("foo\\foo")).is_err());
+        assert!(check(&rules, &String::from("foo<>")).is_err());
+        assert!(check(&rules, &String::from("foo*@")).is_err());
+        assert!(check(&rules, &String::from("A.B")).is_err());
+        assert!(check(&rules, &String::from("S.T.A.L.K.E.R.")).is_err());
     }
 
     #[test]
     fn test_french() {
-        let rules : Rules = load_rules("fr");
-
-        assert!(check(&rules, &String::from("This is absolutely validé.")));
-        assert!(!check(&rules, ""));
-        assert!(!check(&rules, &String::from("\"😊")));
-        assert!(!check(&rules, &String::from("This ends with:")));
-        assert!(!check(&rules, &String::from("This does not end with a period")));
-        assert!(!check(&rules, &String::from("?This does not start with a letter")));
-        assert!(!check(&rules, &String::from("this starts with lowercase")));
-        assert!(!check(&rules, &String::from(" AA ")));
-        assert!(!check(&rules, &String::from("This has broken  space")));
-        assert!(!check(&rules, &String::from("This as well !")));
-        assert!(!check(&rules, &String::from("And this ;")));
-        assert!(!check(&rules, &String::from("This is gonna be way way way way way way way way way way too long")));
-        assert!(!check(&rules, &String::from("Short")));
-        assert!(!check(&rules, &String::from("No!!!")));
-        assert!(!check(&rules, &String::from("This contains 1 number")));
-        assert!(!check(&rules, &String::from("foo\n\nfoo")));
-        assert!(!check(&rules, &String::from("foo<>")));
-        assert!(!check(&rules, &String::from("foo«")));
-        assert!(!check(&rules, &String::from("foo*@")));
-        assert!(!check(&rules, &String::from("A.B")));
-        assert!(!check(&rules, &String::from("S.T.A.L.K.E.R.")));
-        assert!(!check(&rules, &String::from("Some sentence that ends with A.")));
+        let rules = load_rules("fr", None).unwrap();
+
+        assert!(check(&rules, &String::from("This is absolutely validé.")).is_ok());
+        assert!(check(&rules, "").is_err());
+        assert!(check(&rules, &String::from("\"😊")).is_err());
+        assert!(check(&rules, &String::from("This ends with:")).is_err());
+        assert!(check(&rules, &String::from("This does not end with a period")).is_err());
+        assert!(check(&rules, &String::from("?This does not start with a letter")).is_err());
+        assert!(check(&rules, &String::from("this starts with lowercase")).is_err());
+        assert!(check(&rules, &String::from(" AA ")).is_err());
+        assert!(check(&rules, &String::from("This has broken  space")).is_err());
+        assert!(check(&rules, &String::from("This as well !")).is_err());
+        assert!(check(&rules, &String::from("And this ;")).is_err());
+        assert!(check(&rules, &String::from("This is gonna be way way way way way way way way way way too long")).is_err());
+        assert!(check(&rules, &String::from("Short")).is_err());
+        assert!(check(&rules, &String::from("No!!!")).is_err());
+        assert!(check(&rules, &String::from("This contains 1 number")).is_err());
+        assert!(check(&rules, &String::from("foo\n\nfoo")).is_err());
+        assert!(check(&rules, &String::from("foo<>")).is_err());
+        assert!(check(&rules, &String::from("foo«")).is_err());
+        assert!(check(&rules, &String::from("foo*@")).is_err());
+        assert!(check(&rules, &String::from("A.B")).is_err());
+        assert!(check(&rules, &String::from("S.T.A.L.K.E.R.")).is_err());
+        assert!(check(&rules, &String::from("Some sentence that ends with A.")).is_err());
     }
 
     #[test]
     fn test_german() {
-        let rules : Rules = load_rules("de");
-
-        assert!(check(&rules, &String::from("Dies ist ein korrekter Satz.")));
-        assert!(!check(&rules, &String::from("Satzzeichen in der Mitte. Wird nicht akzeptiert.")));
-        assert!(!check(&rules, &String::from("Satzzeichen in der Mitte? Wird nicht akzeptiert.")));
-        assert!(!check(&rules, &String::from("Satzzeichen in der Mitte! Wird nicht akzeptiert.")));
-        assert!(!check(&rules, &String::from("Französische Satzzeichen werden ignorierté.")));
-        assert!(!check(&rules, &String::from("Andere Satzzeichen wie Åblabla werden auch ignoriert.")));
-        assert!(!check(&rules, &String::from("Γεια σας")));
-        assert!(!check(&rules, &String::from("Sätze dürfen keine Wörter mit nur einem B Buchstaben haben.")));
-        assert!(!check(&rules, &String::from("A auch nicht am Anfang.")));
-        assert!(!check(&rules, &String::from("Oder am Ende e.")));
-        assert!(!check(&rules, &String::from("AmSi ist eine schwarze Masse, isomorph mit LaSi")));
-        assert!(!check(&rules, &String::from("Kein deutsches Wort: ambiguous.")));
-        assert!(!check(&rules, &String::from("Zweiter Paragraph im AktG")));
-        assert!(!check(&rules, &String::from("Mai in der Domkirche von Badajoz statt.")));
-        assert!(!check(&rules, &String::from("Keine Abkürzung mit Umlauten BÄK")));
+        let rules = load_rules("de", None).unwrap();
+
+        assert!(check(&rules, &String::from("Dies ist ein korrekter Satz.")).is_ok());
+        assert!(check(&rules, &String::from("Satzzeichen in der Mitte. Wird nicht akzeptiert.")).is_err());
+        assert!(check(&rules, &String::from("Satzzeichen in der Mitte? Wird nicht akzeptiert.")).is_err());
+        assert!(check(&rules, &String::from("Satzzeichen in der Mitte! Wird nicht akzeptiert.")).is_err());
+        assert!(check(&rules, &String::from("Französische Satzzeichen werden ignorierté.")).is_err());
+        assert!(check(&rules, &String::from("Andere Satzzeichen wie Åblabla werden auch ignoriert.")).is_err());
+        assert!(check(&rules, &String::from("Γεια σας")).is_err());
+        assert!(check(&rules, &String::from("Sätze dürfen keine Wörter mit nur einem B Buchstaben haben.")).is_err());
+        assert!(check(&rules, &String::from("A auch nicht am Anfang.")).is_err());
+        assert!(check(&rules, &String::from("Oder am Ende e.")).is_err());
+        assert!(check(&rules, &String::from("AmSi ist eine schwarze Masse, isomorph mit LaSi")).is_err());
+        assert!(check(&rules, &String::from("Kein deutsches Wort: ambiguous.")).is_err());
+        assert!(check(&rules, &String::from("Zweiter Paragraph im AktG")).is_err());
+        assert!(check(&rules, &String::from("Mai in der Domkirche von Badajoz statt.")).is_err());
+        assert!(check(&rules, &String::from("Keine Abkürzung mit Umlauten BÄK")).is_err());
     }
 
     #[test]
     fn test_hungarian() {
-        let rules : Rules = load_rules("hu");
-
-        assert!(check(&rules, &String::from("A egyik legkiválóbb uralkodójaként tartják számon.")));
-        assert!(!check(&rules, &String::from("A BBC Rádió rádiójátékot készített belőle.")));
-        assert!(!check(&rules, &String::from("A BD fejlesztései miatt verziószámmal is találkozhatunk.")));
-        assert!(!check(&rules, &String::from("A BCS-elmélet más fermionok közti kölcsönhatások leírására is alkalmas.")));
-        assert!(!check(&rules, &String::from("A BKV-nál a kocsik elbontásáról döntöttek.")));
-        assert!(!check(&rules, &String::from("A BL-ben ötször játszhatott.")));
-        assert!(!check(&rules, &String::from("A B-döntőt hat résztvevővel rendezték.")));
-        assert!(!check(&rules, &String::from("A -ház egyik legkiválóbb uralkodójaként tartják számon.")));
+        let rules = load_rules("hu", None).unwrap();
+
+        assert!(check(&rules, &String::from("A egyik legkiválóbb uralkodójaként tartják számon.")).is_ok());
+        assert!(check(&rules, &String::from("A BBC Rádió rádiójátékot készített belőle.")).is_err());
+        assert!(check(&rules, &String::from("A BD fejlesztései miatt verziószámmal is találkozhatunk.")).is_err());
+        assert!(check(&rules, &String::from("A BCS-elmélet más fermionok közti kölcsönhatások leírására is alkalmas.")).is_err());
+        assert!(check(&rules, &String::from("A BKV-nál a kocsik elbontásáról döntöttek.")).is_err());
+        assert!(check(&rules, &String::from("A BL-ben ötször játszhatott.")).is_err());
+        assert!(check(&rules, &String::from("A B-döntőt hat résztvevővel rendezték.")).is_err());
+        assert!(check(&rules, &String::from("A -ház egyik legkiválóbb uralkodójaként tartják számon.")).is_err());
     }
 }
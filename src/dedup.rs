@@ -0,0 +1,405 @@
+use crate::rules::Rules;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
+use std::hash::{Hash, Hasher};
+use unicode_normalization::char::is_combining_mark;
+use unicode_normalization::UnicodeNormalization;
+
+// Number of characters per shingle used to compare sentences for near duplicates.
+// Character shingles work better than word shingles for templated sentences, as
+// a single swapped name only affects a few of them.
+const SHINGLE_SIZE: usize = 5;
+// The MinHash signature is split into bands for locality sensitive hashing. Two
+// sentences are only compared if at least one band of their signatures is equal.
+const BANDS: usize = 16;
+const ROWS_PER_BAND: usize = 4;
+const SIGNATURE_SIZE: usize = BANDS * ROWS_PER_BAND;
+
+#[derive(Debug, Default, Clone, Copy)]
+pub struct Normalization {
+    pub unicode: bool,
+    pub case_insensitive: bool,
+    pub ignore_punctuation: bool,
+}
+
+impl Normalization {
+    pub fn from_rules(rules: &Rules) -> Self {
+        Self {
+            unicode: rules.dedup_unicode_normalization,
+            case_insensitive: rules.dedup_case_insensitive,
+            ignore_punctuation: rules.dedup_ignore_punctuation,
+        }
+    }
+
+    // The key used to decide if two sentences are the same
+    pub fn key(&self, sentence: &str) -> String {
+        let mut key = sentence.trim().to_string();
+        if self.unicode {
+            key = key.nfc().collect();
+        }
+        if self.case_insensitive {
+            key = key.to_lowercase();
+        }
+        if self.ignore_punctuation {
+            key = key
+                .chars()
+                .filter(|c| c.is_alphanumeric() || c.is_whitespace() || is_combining_mark(*c))
+                .collect::<String>()
+                .split_whitespace()
+                .collect::<Vec<_>>()
+                .join(" ");
+        }
+
+        key
+    }
+}
+
+type Signature = [u64; SIGNATURE_SIZE];
+
+struct Cluster {
+    signature: Signature,
+    count: usize,
+}
+
+// Groups sentences whose estimated Jaccard similarity is above the threshold,
+// and allows at most `max_count` sentences per group.
+struct NearDuplicates {
+    threshold: f64,
+    max_count: usize,
+    clusters: Vec<Cluster>,
+    buckets: HashMap<(usize, u64), Vec<usize>>,
+}
+
+impl NearDuplicates {
+    fn find_cluster(&self, signature: &Signature) -> Option<usize> {
+        let mut compared = HashSet::new();
+        for (band, band_hash) in band_hashes(signature).into_iter().enumerate() {
+            for &cluster_index in self.buckets.get(&(band, band_hash)).into_iter().flatten() {
+                if !compared.insert(cluster_index) {
+                    continue;
+                }
+
+                if similarity(signature, &self.clusters[cluster_index].signature) >= self.threshold {
+                    return Some(cluster_index);
+                }
+            }
+        }
+
+        None
+    }
+
+    fn is_full(&self, signature: &Signature) -> bool {
+        self.find_cluster(signature)
+            .map(|cluster_index| self.clusters[cluster_index].count >= self.max_count)
+            .unwrap_or(false)
+    }
+
+    fn insert(&mut self, signature: Signature) {
+        match self.find_cluster(&signature) {
+            Some(cluster_index) => self.clusters[cluster_index].count += 1,
+            None => {
+                let cluster_index = self.clusters.len();
+                for (band, band_hash) in band_hashes(&signature).into_iter().enumerate() {
+                    self.buckets.entry((band, band_hash)).or_default().push(cluster_index);
+                }
+                self.clusters.push(Cluster { signature, count: 1 });
+            },
+        }
+    }
+}
+
+// Bot generated stub articles all use the same sentences with a different place
+// or person name. Masking capitalized words and numbers gives us the template
+// ("skeleton") of a sentence, so that we can count how often it was used.
+// Consecutive masked words are merged to also cover names with multiple words.
+// The first word is capitalized anyway, so it's never masked as a name. Languages
+// capitalizing all nouns can turn off masking capitalized words altogether.
+pub fn skeleton(sentence: &str, mask_capitalized: bool) -> String {
+    let mut tokens: Vec<&str> = vec![];
+    for (index, token) in sentence.split_whitespace().enumerate() {
+        let first_letter = token.chars().find(|c| c.is_alphanumeric());
+        let masked = match first_letter {
+            Some(letter) if letter.is_numeric() => "<NUMBER>",
+            Some(letter) if letter.is_uppercase() && mask_capitalized && index > 0 => "<NAME>",
+            _ => token,
+        };
+        if masked.starts_with('<') && tokens.last() == Some(&masked) {
+            continue;
+        }
+        tokens.push(masked);
+    }
+
+    tokens.join(" ")
+}
+
+// Keeps track of all extracted sentences to prevent duplicates. Sentences are
+// compared by their normalized key, and optionally also capped if too many
+// similar sentences or sentences with the same template were already extracted.
+pub struct DuplicateFilter {
+    normalization: Normalization,
+    seen: HashSet<String>,
+    near_duplicates: Option<NearDuplicates>,
+    max_sentences_per_template: usize,
+    template_mask_capitalized: bool,
+    templates: HashMap<String, usize>,
+}
+
+impl Default for DuplicateFilter {
+    fn default() -> Self {
+        Self {
+            normalization: Normalization::default(),
+            seen: HashSet::new(),
+            near_duplicates: None,
+            max_sentences_per_template: usize::MAX,
+            template_mask_capitalized: true,
+            templates: HashMap::new(),
+        }
+    }
+}
+
+impl DuplicateFilter {
+    pub fn from_rules(rules: &Rules) -> Self {
+        let near_duplicates = if rules.near_duplicate_threshold > 0.0 {
+            Some(NearDuplicates {
+                threshold: rules.near_duplicate_threshold,
+                max_count: rules.near_duplicate_max_count,
+                clusters: vec![],
+                buckets: HashMap::new(),
+            })
+        } else {
+            None
+        };
+
+        Self {
+            normalization: Normalization::from_rules(rules),
+            seen: HashSet::new(),
+            near_duplicates,
+            max_sentences_per_template: rules.max_sentences_per_template,
+            template_mask_capitalized: rules.template_mask_capitalized,
+            templates: HashMap::new(),
+        }
+    }
+
+    pub fn is_duplicate(&self, sentence: &str) -> bool {
+        let key = self.normalization.key(sentence);
+        if self.seen.contains(&key) {
+            return true;
+        }
+
+        if self.max_sentences_per_template != usize::MAX {
+            let count = self.templates.get(&skeleton(sentence, self.template_mask_capitalized)).copied().unwrap_or(0);
+            if count >= self.max_sentences_per_template {
+                return true;
+            }
+        }
+
+        match &self.near_duplicates {
+            Some(near_duplicates) => near_duplicates.is_full(&signature(&key)),
+            None => false,
+        }
+    }
+
+    pub fn insert(&mut self, sentence: &str) {
+        if self.max_sentences_per_template != usize::MAX {
+            *self.templates.entry(skeleton(sentence, self.template_mask_capitalized)).or_insert(0) += 1;
+        }
+        let key = self.normalization.key(sentence);
+        if let Some(near_duplicates) = self.near_duplicates.as_mut() {
+            near_duplicates.insert(signature(&key));
+        }
+        self.seen.insert(key);
+    }
+
+    pub fn key(&self, sentence: &str) -> String {
+        self.normalization.key(sentence)
+    }
+}
+
+fn shingles(key: &str) -> Vec<String> {
+    let chars = key.chars().collect::<Vec<_>>();
+    if chars.len() <= SHINGLE_SIZE {
+        return vec![key.to_string()];
+    }
+
+    chars.windows(SHINGLE_SIZE).map(|window| window.iter().collect()).collect()
+}
+
+// splitmix64 finalizer, used to derive the different MinHash functions
+// from a single hash per shingle
+fn mix(value: u64) -> u64 {
+    let mut z = value;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    z ^ (z >> 31)
+}
+
+fn signature(key: &str) -> Signature {
+    let mut signature = [u64::MAX; SIGNATURE_SIZE];
+    for shingle in shingles(key) {
+        let mut hasher = DefaultHasher::new();
+        shingle.hash(&mut hasher);
+        let shingle_hash = hasher.finish();
+        for (index, value) in signature.iter_mut().enumerate() {
+            let hash = mix(shingle_hash.wrapping_add((index as u64).wrapping_mul(0x9e3779b97f4a7c15)));
+            if hash < *value {
+                *value = hash;
+            }
+        }
+    }
+
+    signature
+}
+
+fn band_hashes(signature: &Signature) -> Vec<u64> {
+    signature.chunks(ROWS_PER_BAND).map(|band| {
+        let mut hasher = DefaultHasher::new();
+        band.hash(&mut hasher);
+        hasher.finish()
+    }).collect()
+}
+
+// Estimated Jaccard similarity of the shingles of two sentences
+fn similarity(first: &Signature, second: &Signature) -> f64 {
+    let equal = first.iter().zip(second.iter()).filter(|(a, b)| a == b).count();
+    equal as f64 / SIGNATURE_SIZE as f64
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_key_default() {
+        let normalization = Normalization::default();
+
+        assert_eq!(normalization.key("  This is a Test.  "), "This is a Test.");
+    }
+
+    #[test]
+    fn test_key_unicode() {
+        let normalization = Normalization {
+            unicode: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalization.key("Cafe\u{0301}"), normalization.key("Caf\u{00e9}"));
+    }
+
+    #[test]
+    fn test_key_case_insensitive() {
+        let normalization = Normalization {
+            case_insensitive: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalization.key("This is a Test."), "this is a test.");
+    }
+
+    #[test]
+    fn test_key_ignore_punctuation() {
+        let normalization = Normalization {
+            ignore_punctuation: true,
+            ..Default::default()
+        };
+
+        assert_eq!(normalization.key("„This“  is a test!"), normalization.key("\"This\" is a test."));
+        assert_eq!(normalization.key("नमस्ते दुनिया।"), "नमस्ते दुनिया");
+    }
+
+    #[test]
+    fn test_exact_duplicates() {
+        let mut filter = DuplicateFilter::default();
+        filter.insert("This is a test.");
+
+        assert!(filter.is_duplicate(" This is a test. "));
+        assert!(!filter.is_duplicate("This is a Test."));
+    }
+
+    #[test]
+    fn test_normalized_duplicates() {
+        let rules = Rules {
+            dedup_case_insensitive: true,
+            dedup_ignore_punctuation: true,
+            ..Default::default()
+        };
+        let mut filter = DuplicateFilter::from_rules(&rules);
+        filter.insert("This is a test.");
+
+        assert!(filter.is_duplicate("this is a test!"));
+    }
+
+    #[test]
+    fn test_near_duplicates_capped() {
+        let rules = Rules {
+            near_duplicate_threshold: 0.5,
+            near_duplicate_max_count: 2,
+            ..Default::default()
+        };
+        let mut filter = DuplicateFilter::from_rules(&rules);
+        filter.insert("Oberdorf is a village in the Bern district of Switzerland.");
+        assert!(!filter.is_duplicate("Unterdorf is a village in the Bern district of Switzerland."));
+        filter.insert("Unterdorf is a village in the Bern district of Switzerland.");
+
+        assert!(filter.is_duplicate("Mitteldorf is a village in the Bern district of Switzerland."));
+        assert!(!filter.is_duplicate("The river flows through a wide valley before it reaches the lake."));
+    }
+
+    #[test]
+    fn test_skeleton() {
+        assert_eq!(
+            skeleton("The village of Oberdorf lies in the Bern district of Switzerland.", true),
+            skeleton("The village of Bad Unterdorf lies in the Emmental district of Switzerland.", true),
+        );
+        assert_eq!(skeleton("It has 1,234 inhabitants (2010).", true), "It has <NUMBER> inhabitants <NUMBER>");
+        assert_eq!(skeleton("2010 lived Foo Bar here.", true), "<NUMBER> lived <NAME> here.");
+        assert_ne!(skeleton("Foo is a village.", true), skeleton("Bar is a village.", true));
+        assert_ne!(skeleton("The Foo is a village.", true), skeleton("The Foo is a town.", true));
+    }
+
+    #[test]
+    fn test_skeleton_without_capitalized() {
+        assert_eq!(
+            skeleton("Das Dorf hat 1234 Einwohner und eine Kirche.", false),
+            "Das Dorf hat <NUMBER> Einwohner und eine Kirche."
+        );
+        assert_ne!(
+            skeleton("Das Dorf hat eine Kirche.", false),
+            skeleton("Das Dorf hat eine Schule.", false),
+        );
+    }
+
+    #[test]
+    fn test_max_sentences_per_template() {
+        let rules = Rules {
+            max_sentences_per_template: 2,
+            ..Default::default()
+        };
+        let mut filter = DuplicateFilter::from_rules(&rules);
+        filter.insert("The village Oberdorf is in Bern.");
+        filter.insert("The village Unterdorf is in Bern.");
+
+        assert!(filter.is_duplicate("The village Mitteldorf is in Emmental."));
+        assert!(!filter.is_duplicate("The town Mitteldorf is in Emmental."));
+
+        let rules = Rules {
+            template_mask_capitalized: false,
+            ..rules
+        };
+        let mut filter = DuplicateFilter::from_rules(&rules);
+        filter.insert("Das Dorf Oberdorf liegt in Bern.");
+        filter.insert("Das Dorf Unterdorf liegt in Bern.");
+
+        assert!(!filter.is_duplicate("Das Dorf Mitteldorf liegt in Emmental."));
+        filter.insert("Das Dorf hat 100 Einwohner.");
+        filter.insert("Das Dorf hat 200 Einwohner.");
+        assert!(filter.is_duplicate("Das Dorf hat 300 Einwohner."));
+    }
+
+    #[test]
+    fn test_similarity() {
+        let first = signature("this is a sentence");
+
+        assert_eq!(similarity(&first, &signature("this is a sentence")), 1.0);
+        assert!(similarity(&first, &signature("something completely different")) < 0.2);
+    }
+}
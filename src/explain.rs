@@ -0,0 +1,77 @@
+use crate::checker::check_all;
+use crate::extractor::split_sentences;
+use crate::replacer::replace_strings;
+use crate::rules::{load_rules, CompiledRules};
+use crate::segmenter::segmenter_for;
+use std::io::{self, Write};
+
+fn write_checks(writer: &mut impl Write, rules: &CompiledRules, sentence: &str) -> io::Result<()> {
+    let results = check_all(rules, sentence);
+    for (rule, result) in results.iter() {
+        match result {
+            Ok(()) => writeln!(writer, "  pass  {}", rule)?,
+            Err(rejection) => writeln!(writer, "  FAIL  {}", rejection)?,
+        }
+    }
+
+    match results.into_iter().find_map(|(_, result)| result.err()) {
+        Some(rejection) => writeln!(writer, "  => rejected by {}", rejection.rule()),
+        None => writeln!(writer, "  => accepted"),
+    }
+}
+
+// Shows every step a sentence goes through during an extraction
+pub fn explain(language: &str, rules_dir: Option<&str>, sentence: &str) -> Result<(), String> {
+    let rules = load_rules(language, rules_dir)?;
+    let segmenter_name = if rules.segmenter.is_empty() { "rust-punkt" } else { &rules.segmenter };
+    let segmenter = segmenter_for(&rules, language, rules_dir)?;
+    let sentences = split_sentences(&rules, sentence, segmenter.as_ref());
+
+    let mut writer = io::stdout().lock();
+    let mut write = || -> io::Result<()> {
+        writeln!(writer, "After replacements: {:?}", replace_strings(&rules, sentence))?;
+        writeln!(writer, "Split by {} into {} sentence(s)", segmenter_name, sentences.len())?;
+        for (index, sentence) in sentences.iter().enumerate() {
+            writeln!(writer, "\nSentence {}: {:?}", index + 1, sentence.trim())?;
+            write_checks(&mut writer, &rules, sentence)?;
+        }
+        Ok(())
+    };
+    write().map_err(|e| format!("{}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rules;
+
+    fn explanation(rules: &CompiledRules, sentence: &str) -> String {
+        let mut output = vec![];
+        write_checks(&mut output, rules, sentence).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_write_checks() {
+        let rules = Rules {
+            max_word_count: 3,
+            other_patterns: vec![String::from("foo")],
+            ..Default::default()
+        }.compile().unwrap();
+
+        let output = explanation(&rules, "This has foo too");
+        assert!(output.contains("  pass  min_word_count\n"));
+        assert!(output.contains("  FAIL  max_word_count\n"));
+        assert!(output.contains("  FAIL  other_patterns (\"foo\")\n"));
+        assert!(output.ends_with("  => rejected by max_word_count\n"));
+    }
+
+    #[test]
+    fn test_write_checks_accepted() {
+        let rules = Rules::default().compile().unwrap();
+
+        let output = explanation(&rules, "This is fine");
+        assert!(!output.contains("FAIL"));
+        assert!(output.ends_with("  => accepted\n"));
+    }
+}
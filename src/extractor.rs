@@ -1,65 +1,234 @@
-use crate::config::Config;
 use crate::replacer;
-use crate::checker;
-use crate::loaders::Loader;
-use crate::rules::{load_rules, Rules};
-use crate::segmenter::split_sentences_with_python;
+use crate::checker::{self, CheckResult};
+use crate::dedup::DuplicateFilter;
+use crate::known_sentences::{open_known_sentences_writer, read_known_sentences};
+use crate::loaders::{Loader, Text};
+use crate::output::{OutputOptions, SentenceSource};
+use crate::rules::{load_rules, CompiledRules, Rules};
+use crate::segmenter::{segmenter_for, Segmenter};
+use crate::stats::RejectionStats;
 use glob::glob;
-use punkt::params::Standard;
-use punkt::{SentenceTokenizer, TrainingData};
-use rand::Rng;
-use rand::rngs::ThreadRng;
-use std::collections::HashSet;
+use rand::{Rng, SeedableRng};
+use rand::rngs::StdRng;
+use rayon::prelude::*;
+use std::collections::{HashMap, HashSet};
 use std::fs::File;
-use std::io::Read;
+use std::io::{BufWriter, Read, Write};
 use std::path::Path;
 use std::path::PathBuf;
+use std::sync::{mpsc, Mutex};
+
+// Reason written to the rejected sentences file for sentences that were extracted before
+const DUPLICATE_REASON: &str = "duplicate";
+
+#[derive(Default)]
+pub struct ExtractOptions {
+    pub no_check: bool,
+    // Directory to read the rules from, the embedded rules are used for missing files
+    pub rules_dir: Option<String>,
+    pub filter_list_path: String,
+    pub rejection_stats_path: Option<String>,
+    pub rejected_out_path: Option<String>,
+    pub output: OutputOptions,
+    // Seed for the random sentence selection, a random one is used if not given
+    pub seed: Option<u64>,
+    // Number of files read and threads splitting and checking texts in parallel
+    pub jobs: usize,
+    // Files with sentences that should never be extracted again
+    pub known_sentences_paths: Vec<String>,
+    // Append newly extracted sentences to the first known sentences file
+    pub update_known: bool,
+}
+
+// A text as returned by the loader, split into sentences which already have been
+// checked against the rules. This is the expensive part of the extraction and
+// can run in parallel.
+struct PreparedText {
+    text: Text,
+    sentences_pool: Vec<String>,
+    check_results: Vec<CheckResult>,
+}
 
-pub fn extract(loader: impl Loader, no_check: bool, filter_list_path: String) -> Result<(), String> {
+// Prints the extracted sentences to stdout in the configured output format
+pub fn extract(loader: impl Loader + Sync, options: ExtractOptions) -> Result<(), String> {
+    let output = options.output.clone();
+    let language = loader.get_config().language.clone();
+    if let Some(header) = output.header() {
+        println!("{}", header);
+    }
+
+    extract_with(loader, options, |sentence, source| {
+        println!("{}", output.format_sentence(sentence, source, &language));
+        Ok(())
+    })
+}
+
+// Runs an extraction and passes every extracted sentence together with where it came
+// from to the callback, in the order they are extracted. An error returned by the
+// callback stops the extraction.
+pub fn extract_with(
+    loader: impl Loader + Sync,
+    options: ExtractOptions,
+    mut on_sentence: impl FnMut(&str, &SentenceSource) -> Result<(), String>,
+) -> Result<(), String> {
     let config = loader.get_config();
-    let rules = load_rules(&config.language);
-    let training_data = get_training_data(&config.language);
-    let filtered_titles = read_filtered_titles(filter_list_path);
-    let mut existing_sentences = HashSet::new();
+    let rules = load_rules(&config.language, options.rules_dir.as_deref())?;
+    let segmenter = segmenter_for(&rules, &config.language, options.rules_dir.as_deref())?;
+    let filtered_titles = read_filtered_titles(options.filter_list_path);
+    let mut existing_sentences = DuplicateFilter::from_rules(&rules);
+    for sentence in read_known_sentences(&options.known_sentences_paths)? {
+        existing_sentences.insert(&sentence);
+    }
+    let mut known_writer = match options.known_sentences_paths.first() {
+        Some(path) if options.update_known => Some(open_known_sentences_writer(path)?),
+        _ => None,
+    };
+    let mut rejection_stats = RejectionStats::default();
+    let seed = options.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+    let mut rejected_writer = create_rejected_writer(&options.rejected_out_path)?;
     let mut char_count = 0;
     let mut sentence_count = 0;
-    let file_names = load_file_names(&config.directory, &config.file_prefix).unwrap();
-    for file_name in file_names {
-        eprintln!("file_name = {:?}", file_name.to_string_lossy());
-        let texts = loader.load(&file_name, &filtered_titles)?;
-        for text in texts {
-            let sentences = get_sentences(
-                &rules,
-                &text,
-                &existing_sentences,
-                &training_data,
-                config,
-                no_check,
-            );
-
-            for sentence in sentences {
-                println!("{}", sentence);
-                char_count += sentence.chars().count();
-                sentence_count += 1;
-                existing_sentences.insert(sentence);
+    let file_names = load_file_names(&config.directory, &config.file_prefix)?;
+    let jobs = options.jobs.max(1);
+    let no_check = options.no_check;
+    let thread_pool = rayon::ThreadPoolBuilder::new()
+        .num_threads(jobs)
+        .build()
+        .map_err(|e| format!("{}", e))?;
+
+    // Up to `jobs` files are read in parallel, and the texts of every chunk read get
+    // split and checked on the thread pool. Picking the sentences depends on the
+    // sentences we already extracted and on the random number generator, so this
+    // happens sequentially in the order of the files and texts. This keeps the output
+    // the same, no matter how many jobs are used. Every file only gets one chunk ahead
+    // of the picking, which keeps the memory use low for big dumps.
+    let mut files = vec![];
+    let mut receivers = vec![];
+    for file_name in file_names.iter() {
+        let (sender, receiver) = mpsc::sync_channel::<Result<Vec<PreparedText>, String>>(1);
+        files.push((file_name, sender));
+        receivers.push(receiver);
+    }
+    // Files are handed out in order, so the file picked from next is always being read
+    let files = Mutex::new(files.into_iter());
+    std::thread::scope(|scope| {
+        for _ in 0..jobs.min(file_names.len()) {
+            scope.spawn(|| loop {
+                let next_file = files.lock().unwrap().next();
+                let (file_name, sender) = match next_file {
+                    Some(file) => file,
+                    None => break,
+                };
+                let result = loader.load_chunks(file_name, &filtered_titles, &mut |texts| {
+                    let prepared_texts = thread_pool.install(|| {
+                        prepare_texts(texts, &rules, segmenter.as_ref(), no_check)
+                    });
+                    // Only fails if the extraction stopped because of an error
+                    sender.send(Ok(prepared_texts)).map_err(|e| format!("{}", e))
+                });
+                if let Err(e) = result {
+                    if sender.send(Err(e)).is_err() {
+                        break;
+                    }
+                }
+            });
+        }
+
+        for (file_name, receiver) in file_names.iter().zip(receivers) {
+            let file_path = file_name.to_string_lossy();
+            eprintln!("file_name = {:?}", file_path);
+            for prepared_texts in receiver {
+                for PreparedText { text, sentences_pool, check_results } in prepared_texts? {
+                    // Every sentence of the pool is counted, not only the ones looked at while
+                    // picking, so the stats don't depend on the seed or max_sentences_per_text
+                    for rejection in check_results.iter().filter_map(|result| result.as_ref().err()) {
+                        rejection_stats.record(rejection);
+                    }
+
+                    if let Some(writer) = rejected_writer.as_mut() {
+                        for (sentence, reason) in discarded_sentences(&sentences_pool, &check_results, &existing_sentences) {
+                            write_rejected(writer, &sentence, &reason, file_name, &text)?;
+                        }
+                    }
+
+                    let sentences = if no_check {
+                        sentences_pool.into_iter().enumerate().collect()
+                    } else {
+                        let results = sentences_pool.iter().cloned()
+                            .zip(check_results)
+                            .collect::<HashMap<String, CheckResult>>();
+                        pick_sentences(
+                            &rules,
+                            sentences_pool,
+                            &existing_sentences,
+                            config.max_sentences_per_text,
+                            |_, sentence| results[sentence].clone(),
+                            &mut rng,
+                        )
+                    };
+
+                    for (index, sentence) in sentences {
+                        let source = SentenceSource {
+                            loader: loader.get_name(),
+                            file: &file_path,
+                            title: text.title.as_deref(),
+                            id: text.id.as_deref(),
+                            index,
+                        };
+                        on_sentence(&sentence, &source)?;
+                        if let Some(writer) = known_writer.as_mut() {
+                            writeln!(writer, "{}", sentence).map_err(|e| format!("{}", e))?;
+                        }
+                        char_count += sentence.chars().count();
+                        sentence_count += 1;
+                        existing_sentences.insert(&sentence);
+                    }
+                }
             }
+            eprintln!("avg chars per sentence = {:?}", char_count as f64 / f64::from(sentence_count));
+            eprintln!("count = {:?}", sentence_count);
         }
-        eprintln!("avg chars per sentence = {:?}", char_count as f64 / f64::from(sentence_count));
-        eprintln!("count = {:?}", sentence_count);
+
+        Ok::<(), String>(())
+    })?;
+
+    if let Some(mut writer) = rejected_writer {
+        writer.flush().map_err(|e| format!("{}", e))?;
+    }
+    if let Some(mut writer) = known_writer {
+        writer.flush().map_err(|e| format!("{}", e))?;
+    }
+
+    eprintln!("seed = {:?}", seed);
+    rejection_stats.print();
+    if let Some(path) = options.rejection_stats_path {
+        rejection_stats.write_json(&path)?;
+        eprintln!("Wrote rejection stats to {:?}", path);
     }
+
     Ok(())
 }
 
-fn get_sentences(
-    rules: &Rules,
-    text: &str,
-    existing_sentences: &HashSet<String>,
-    training_data: &TrainingData,
-    config: &Config,
+fn prepare_texts(
+    texts: Vec<Text>,
+    rules: &CompiledRules,
+    segmenter: &dyn Segmenter,
     no_check: bool,
-) -> Vec<String> {
-    let sentences_pool: Vec<String>;
+) -> Vec<PreparedText> {
+    texts.into_par_iter().map(|text| {
+        let sentences_pool = split_sentences(rules, &text.content, segmenter);
+        let check_results = if no_check {
+            vec![]
+        } else {
+            sentences_pool.iter().map(|sentence| checker::check(rules, sentence)).collect()
+        };
+
+        PreparedText { text, sentences_pool, check_results }
+    }).collect()
+}
 
+pub fn split_sentences(rules: &CompiledRules, text: &str, segmenter: &dyn Segmenter) -> Vec<String> {
     // We want to apply the replacements before we split into sentences, as otherwise
     // the segmentation would not take the replacement into account. This for example
     // would lead to rust-punkt splitting sentences wrongly, while with the replacement
@@ -71,39 +240,17 @@ fn get_sentences(
     // "Hi Mister Smith, how are you?"
     let replaced_text = replacer::replace_strings(rules, text);
 
-    if rules.segmenter != *"" {
-        if rules.segmenter == "python" {
-            sentences_pool = split_sentences_with_python(&config.language, &replaced_text);
-        } else {
-            panic!("Segmenter {} is not yet supported!", rules.segmenter);
-        }
-    } else {
-        // we use rust-punkt as segmenter by default
-        sentences_pool = SentenceTokenizer::<Standard>::new(&replaced_text, training_data)
-            .map(|item| { String::from(item) })
-            .collect();
-    }
-
-    if no_check {
-        sentences_pool
-    } else {
-        pick_sentences(
-            rules,
-            sentences_pool,
-            existing_sentences,
-            config.max_sentences_per_text,
-            checker::check,
-        )
-    }
+    segmenter.split(&replaced_text)
 }
 
 fn pick_sentences(
     rules: &Rules,
     sentences_pool: Vec<String>,
-    existing_sentences: &HashSet<String>,
+    existing_sentences: &DuplicateFilter,
     amount: usize,
-    mut predicate: impl FnMut(&Rules, &str) -> bool
-) -> Vec<String> {
+    mut predicate: impl FnMut(&Rules, &str) -> CheckResult,
+    rng: &mut impl Rng,
+) -> Vec<(usize, String)> {
     let total_in_pool = sentences_pool.len();
 
     // We do not extract if the total is below the max amount.
@@ -116,39 +263,63 @@ fn pick_sentences(
     // If we're allowed to pick all sentences, we do not need to
     // select randomly
     if amount == usize::MAX {
-        return sentences_pool.iter().filter(|&sentence| {
-            let not_already_chosen = !existing_sentences.contains(sentence);
-            predicate(rules, sentence) && not_already_chosen
-        }).map(|sentence| {
-            sentence.trim().to_string()
+        return sentences_pool.iter().enumerate().filter(|&(_, sentence)| {
+            let not_already_chosen = !existing_sentences.is_duplicate(sentence);
+            predicate(rules, sentence).is_ok() && not_already_chosen
+        }).map(|(index, sentence)| {
+            (index, sentence.trim().to_string())
         }).collect::<Vec<_>>();
     }
 
     let mut iteration = 0;
-    let mut chosen_sentences = vec![];
+    let mut chosen_sentences: Vec<(usize, String)> = vec![];
     let mut used_indexes = vec![];
     let mut still_has_sentences_to_search = true;
     while chosen_sentences.len() < amount && still_has_sentences_to_search {
-        let rng = rand::thread_rng();
         let random_index: usize = get_not_yet_used_index(rng, total_in_pool - 1, &used_indexes);
         used_indexes.push(random_index);
 
         let sentence = &sentences_pool[random_index];
-        let not_already_chosen = !existing_sentences.contains(sentence);
-        if predicate(rules, sentence) && not_already_chosen {
-            chosen_sentences.push(sentence.trim().to_string());
-            chosen_sentences.sort();
-            chosen_sentences.dedup();
+        let not_already_chosen = !existing_sentences.is_duplicate(sentence);
+        if predicate(rules, sentence).is_ok() && not_already_chosen {
+            let trimmed = sentence.trim().to_string();
+            let key = existing_sentences.key(&trimmed);
+            if !chosen_sentences.iter().any(|(_, chosen)| existing_sentences.key(chosen) == key) {
+                chosen_sentences.push((random_index, trimmed));
+            }
         }
 
         iteration += 1;
         still_has_sentences_to_search = iteration < total_in_pool;
     }
 
+    // Keep the order in which the sentences appear in the text
+    chosen_sentences.sort();
     chosen_sentences
 }
 
-fn get_not_yet_used_index(mut rng: ThreadRng, max_index: usize, used_indexes: &[usize]) -> usize {
+// Sentences of the pool that the rules rejected or that are duplicates, together with
+// the reason. Unlike picking, which stops once it has enough sentences, this covers
+// every sentence of the pool, so the result does not depend on the random selection.
+fn discarded_sentences(
+    sentences_pool: &[String],
+    check_results: &[CheckResult],
+    existing_sentences: &DuplicateFilter,
+) -> Vec<(String, String)> {
+    let mut seen = HashSet::new();
+    sentences_pool.iter().zip(check_results).filter_map(|(sentence, result)| {
+        let trimmed = sentence.trim();
+        match result {
+            Err(rejection) => Some((trimmed.to_string(), rejection.to_string())),
+            Ok(()) if existing_sentences.is_duplicate(trimmed) || !seen.insert(existing_sentences.key(trimmed)) => {
+                Some((trimmed.to_string(), String::from(DUPLICATE_REASON)))
+            },
+            Ok(()) => None,
+        }
+    }).collect()
+}
+
+fn get_not_yet_used_index(rng: &mut impl Rng, max_index: usize, used_indexes: &[usize]) -> usize {
     let mut index = rng.gen_range(0..=max_index);
     let mut already_used = used_indexes.contains(&index);
     while already_used {
@@ -159,30 +330,7 @@ fn get_not_yet_used_index(mut rng: ThreadRng, max_index: usize, used_indexes: &[
     index
 }
 
-fn get_training_data(language: &str) -> TrainingData {
-    match language {
-        "cs" => TrainingData::czech(),
-        "de" => TrainingData::german(),
-        "dk" => TrainingData::danish(),
-        "el" => TrainingData::greek(),
-        "en" => TrainingData::english(),
-        "es" => TrainingData::spanish(),
-        "et" => TrainingData::estonian(),
-        "fi" => TrainingData::finnish(),
-        "fr" => TrainingData::french(),
-        "it" => TrainingData::italian(),
-        "nl" => TrainingData::dutch(),
-        "no" => TrainingData::norwegian(),
-        "pl" => TrainingData::polish(),
-        "pt" => TrainingData::portuguese(),
-        "se" => TrainingData::swedish(),
-        "sl" => TrainingData::slovene(),
-        "tr" => TrainingData::turkish(),
-        _ => TrainingData::english(),
-    }
-}
-
-fn load_file_names(dir_name: &str, prefix: &str) -> Result<Vec<PathBuf>, String> {
+pub fn load_file_names(dir_name: &str, prefix: &str) -> Result<Vec<PathBuf>, String> {
     let chart_path = Path::new(dir_name);
     let glob_path = format!("{}/**/{}*", chart_path.to_string_lossy(), prefix);
     glob(&glob_path)
@@ -191,6 +339,38 @@ fn load_file_names(dir_name: &str, prefix: &str) -> Result<Vec<PathBuf>, String>
         .collect::<Result<Vec<PathBuf>, String>>()
 }
 
+fn create_rejected_writer(path: &Option<String>) -> Result<Option<BufWriter<File>>, String> {
+    match path {
+        Some(path) => {
+            eprintln!("Writing rejected sentences to {:?}", path);
+            let mut writer = BufWriter::new(File::create(path).map_err(|e| format!("{}", e))?);
+            writeln!(writer, "sentence\trule\tfile\tarticle").map_err(|e| format!("{}", e))?;
+            Ok(Some(writer))
+        },
+        None => Ok(None),
+    }
+}
+
+// Rejected sentences are written as TSV. Sentences rejected because of a line break
+// would break the format, therefore line breaks and tabs are escaped.
+fn write_rejected(
+    writer: &mut impl Write,
+    sentence: &str,
+    reason: &str,
+    file_name: &Path,
+    text: &Text,
+) -> Result<(), String> {
+    let escaped = sentence.replace('\n', "\\n").replace('\t', "\\t");
+    writeln!(
+        writer,
+        "{}\t{}\t{}\t{}",
+        escaped,
+        reason,
+        file_name.to_string_lossy(),
+        text.title.as_deref().unwrap_or_default(),
+    ).map_err(|e| format!("{}", e))
+}
+
 fn read_filtered_titles(filtered_titles_path: String) -> HashSet<String> {
     if filtered_titles_path.is_empty() {
         return HashSet::new();
@@ -216,22 +396,23 @@ fn read_filtered_titles(filtered_titles_path: String) -> HashSet<String> {
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::checker::Rejection;
 
-    fn check_true(_rules: &Rules, _sentence: &str) -> bool {
-        true
+    fn check_true(_rules: &Rules, _sentence: &str) -> CheckResult {
+        Ok(())
     }
 
-    fn check_false(_rules: &Rules, _sentence: &str) -> bool {
-        false
+    fn check_false(_rules: &Rules, _sentence: &str) -> CheckResult {
+        Err(Rejection::MaxWordCount)
     }
 
     #[test]
     fn test_get_not_yet_used_index() {
-        let rng = rand::thread_rng();
+        let mut rng = StdRng::seed_from_u64(42);
         let max_index = 2;
         let used_indexes = vec![0, 2];
 
-        assert_eq!(get_not_yet_used_index(rng, max_index, &used_indexes), 1);
+        assert_eq!(get_not_yet_used_index(&mut rng, max_index, &used_indexes), 1);
     }
 
     #[test]
@@ -239,11 +420,11 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![];
         let amount = 1;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 0);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 0);
     }
 
     #[test]
@@ -251,7 +432,7 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![
             String::from("Test"),
             String::from("Test2"),
@@ -260,7 +441,39 @@ mod test {
         ];
         let amount = 3;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_false).len(), 0);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_false, &mut StdRng::seed_from_u64(42)).len(), 0);
+    }
+
+    #[test]
+    fn test_discarded_sentences() {
+        let mut existing_sentences = DuplicateFilter::default();
+        existing_sentences.insert("I am already existing");
+        let sentences = vec![
+            String::from("Test"),
+            String::from(" I am already existing "),
+            String::from("Too long"),
+            String::from("Test2"),
+            String::from("Test"),
+        ];
+        let check_results = vec![Ok(()), Ok(()), Err(Rejection::MaxWordCount), Ok(()), Ok(())];
+
+        assert_eq!(discarded_sentences(&sentences, &check_results, &existing_sentences), vec![
+            (String::from("I am already existing"), String::from("duplicate")),
+            (String::from("Too long"), String::from("max_word_count")),
+            (String::from("Test"), String::from("duplicate")),
+        ]);
+    }
+
+    #[test]
+    fn test_write_rejected() {
+        let mut output = vec![];
+        let text = Text {
+            title: Some(String::from("Foo")),
+            ..Default::default()
+        };
+
+        write_rejected(&mut output, "Foo\nbar", &Rejection::ContainsNewline.to_string(), Path::new("AA/wiki_00"), &text).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Foo\\nbar\tcontains_newline\tAA/wiki_00\tFoo\n");
     }
 
     #[test]
@@ -268,7 +481,7 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![
             String::from("Test"),
             String::from("Test2"),
@@ -277,7 +490,45 @@ mod test {
         ];
         let amount = 3;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 3);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 3);
+    }
+
+    #[test]
+    fn test_pick_sentences_keeps_index() {
+        let rules : Rules = Rules {
+            ..Default::default()
+        };
+        let existing_sentences = DuplicateFilter::default();
+        let sentences = vec![
+            String::from("Test"),
+            String::from("Test2"),
+            String::from("Test3"),
+            String::from("Test4"),
+        ];
+        let amount = 3;
+
+        let chosen = pick_sentences(&rules, sentences.clone(), &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42));
+        let indexes = chosen.iter().map(|(index, _)| *index).collect::<Vec<_>>();
+        let mut sorted_indexes = indexes.clone();
+        sorted_indexes.sort();
+        assert_eq!(indexes, sorted_indexes);
+        for (index, sentence) in chosen {
+            assert_eq!(sentences[index], sentence);
+        }
+    }
+
+    #[test]
+    fn test_pick_sentences_same_seed_same_result() {
+        let rules : Rules = Rules {
+            ..Default::default()
+        };
+        let existing_sentences = DuplicateFilter::default();
+        let sentences = (0..20).map(|index| format!("Test{}", index)).collect::<Vec<_>>();
+        let amount = 3;
+
+        let first = pick_sentences(&rules, sentences.clone(), &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(7));
+        let second = pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(7));
+        assert_eq!(first, second);
     }
 
     #[test]
@@ -285,7 +536,7 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![
             String::from("Test"),
             String::from("Test2"),
@@ -295,7 +546,7 @@ mod test {
         ];
         let amount = usize::MAX;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 5);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 5);
     }
 
     #[test]
@@ -303,11 +554,11 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![String::from("Test")];
         let amount = 1;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 0);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 0);
     }
 
     #[test]
@@ -315,7 +566,7 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![
             String::from("Test"),
             String::from("Test"),
@@ -324,7 +575,7 @@ mod test {
         ];
         let amount = 3;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 1);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 1);
     }
 
     #[test]
@@ -332,7 +583,7 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let existing_sentences = HashSet::new();
+        let existing_sentences = DuplicateFilter::default();
         let sentences = vec![
             String::from("Test2"),
             String::from("Test"),
@@ -341,7 +592,7 @@ mod test {
         ];
         let amount = 3;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 2);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 2);
     }
 
     #[test]
@@ -349,16 +600,31 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let mut existing_sentences = HashSet::new();
-        existing_sentences.insert(String::from("I am already existing"));
-        existing_sentences.insert(String::from("I am already existing too"));
+        let mut existing_sentences = DuplicateFilter::default();
+        existing_sentences.insert("I am already existing");
+        existing_sentences.insert("I am already existing too");
         let sentences = vec![
             String::from("I am already existing"),
             String::from("I am already existing too"),
         ];
         let amount = 2;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 0);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 0);
+    }
+
+    #[test]
+    fn test_pick_sentences_no_existing_sentences_untrimmed() {
+        let rules : Rules = Rules {
+            ..Default::default()
+        };
+        let mut existing_sentences = DuplicateFilter::default();
+        existing_sentences.insert("I am already existing");
+        let sentences = vec![
+            String::from(" I am already existing "),
+            String::from("Test"),
+        ];
+
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, usize::MAX, check_true, &mut StdRng::seed_from_u64(42)).len(), 1);
     }
 
     #[test]
@@ -366,9 +632,9 @@ mod test {
         let rules : Rules = Rules {
             ..Default::default()
         };
-        let mut existing_sentences = HashSet::new();
-        existing_sentences.insert(String::from("I am already existing"));
-        existing_sentences.insert(String::from("Me too!"));
+        let mut existing_sentences = DuplicateFilter::default();
+        existing_sentences.insert("I am already existing");
+        existing_sentences.insert("Me too!");
         let sentences = vec![
             String::from("Test"),
             String::from("I am already existing"),
@@ -377,6 +643,26 @@ mod test {
         ];
         let amount = 3;
 
-        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true).len(), 2);
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 2);
+    }
+
+    #[test]
+    fn test_pick_sentences_no_normalized_dupes() {
+        let rules : Rules = Rules {
+            dedup_case_insensitive: true,
+            dedup_ignore_punctuation: true,
+            ..Default::default()
+        };
+        let mut existing_sentences = DuplicateFilter::from_rules(&rules);
+        existing_sentences.insert("I am already existing.");
+        let sentences = vec![
+            String::from("i am already existing!"),
+            String::from("\"Test\""),
+            String::from("Test."),
+            String::from("test"),
+        ];
+        let amount = 3;
+
+        assert_eq!(pick_sentences(&rules, sentences, &existing_sentences, amount, check_true, &mut StdRng::seed_from_u64(42)).len(), 1);
     }
 }
@@ -0,0 +1,90 @@
+use crate::checker::check;
+use crate::replacer::replace_strings;
+use crate::rules::{load_rules, CompiledRules};
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+
+pub struct FilterOptions {
+    // Path to read the sentences from, stdin if not given or "-"
+    pub input_path: Option<String>,
+    // Write every sentence with the verdict and reason as TSV instead of only the accepted ones
+    pub verdicts: bool,
+}
+
+// Every line is one sentence, so there is no segmentation. Returns the number of
+// sentences read and accepted.
+fn filter_lines(
+    rules: &CompiledRules,
+    reader: impl BufRead,
+    writer: &mut impl Write,
+    verdicts: bool,
+) -> io::Result<(usize, usize)> {
+    if verdicts {
+        writeln!(writer, "sentence\tverdict\treason")?;
+    }
+
+    let mut total = 0;
+    let mut accepted = 0;
+    for line in reader.lines() {
+        let sentence = replace_strings(rules, &line?);
+        let result = check(rules, &sentence);
+        let sentence = sentence.trim();
+        total += 1;
+        accepted += usize::from(result.is_ok());
+
+        match (verdicts, result) {
+            (false, Ok(())) => writeln!(writer, "{}", sentence)?,
+            (false, Err(_)) => {},
+            (true, Ok(())) => writeln!(writer, "{}\taccept\t", sentence.replace('\t', "\\t"))?,
+            (true, Err(rejection)) => writeln!(writer, "{}\treject\t{}", sentence.replace('\t', "\\t"), rejection)?,
+        }
+    }
+
+    Ok((total, accepted))
+}
+
+pub fn filter_sentences(language: &str, rules_dir: Option<&str>, options: FilterOptions) -> Result<(), String> {
+    let rules = load_rules(language, rules_dir)?;
+    let reader: Box<dyn BufRead> = match options.input_path.as_deref() {
+        None | Some("-") => Box::new(io::stdin().lock()),
+        Some(path) => Box::new(BufReader::new(File::open(path).map_err(|e| format!("{}: {}", path, e))?)),
+    };
+    let mut writer = BufWriter::new(io::stdout().lock());
+
+    let (total, accepted) = filter_lines(&rules, reader, &mut writer, options.verdicts)
+        .and_then(|counts| writer.flush().map(|_| counts))
+        .map_err(|e| format!("{}", e))?;
+    eprintln!("Accepted {} of {} sentences", accepted, total);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rules;
+
+    fn filter(input: &str, verdicts: bool) -> String {
+        let rules = Rules {
+            max_word_count: 3,
+            replacements: vec![(String::from("Mr."), String::from("Mister"))],
+            ..Default::default()
+        }.compile().unwrap();
+
+        let mut output = vec![];
+        filter_lines(&rules, input.as_bytes(), &mut output, verdicts).unwrap();
+        String::from_utf8(output).unwrap()
+    }
+
+    #[test]
+    fn test_filter_lines() {
+        assert_eq!(filter("Hello Mr. Smith\nThis is too long\n  Fine  \n", false), "Hello Mister Smith\nFine\n");
+    }
+
+    #[test]
+    fn test_filter_lines_verdicts() {
+        assert_eq!(
+            filter("Hello Mr. Smith\nThis is too long\n", true),
+            "sentence\tverdict\treason\nHello Mister Smith\taccept\t\nThis is too long\treject\tmax_word_count\n"
+        );
+    }
+}
@@ -0,0 +1,88 @@
+use std::collections::HashSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{BufWriter, Write};
+
+// Known sentences are sentences we already extracted in an earlier run or which
+// are already part of Common Voice. They are stored with one sentence per line
+// and never get extracted again.
+pub fn read_known_sentences(paths: &[String]) -> Result<HashSet<String>, String> {
+    let mut known_sentences = HashSet::new();
+    for path in paths {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        known_sentences.extend(
+            content
+                .lines()
+                .map(|line| line.trim())
+                .filter(|line| !line.is_empty())
+                .map(String::from)
+        );
+        eprintln!("Read known sentences from {:?}", path);
+    }
+
+    if !paths.is_empty() {
+        eprintln!("Read {:?} known sentences..", known_sentences.len());
+    }
+
+    Ok(known_sentences)
+}
+
+// Opens the known sentences file to append newly extracted sentences to it.
+// If the file does not end with a line break, we add one first so that
+// the first appended sentence does not end up on the last existing line.
+pub fn open_known_sentences_writer(path: &str) -> Result<BufWriter<File>, String> {
+    let needs_line_break = fs::read(path)
+        .map(|content| content.last().map(|last| *last != b'\n').unwrap_or(false))
+        .unwrap_or(false);
+    let file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)
+        .map_err(|e| format!("{}: {}", path, e))?;
+    let mut writer = BufWriter::new(file);
+    if needs_line_break {
+        writeln!(writer).map_err(|e| format!("{}", e))?;
+    }
+
+    eprintln!("Appending extracted sentences to {:?}", path);
+    Ok(writer)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::temp_dir_with;
+
+    fn path(dir: &tempfile::TempDir, file_name: &str) -> String {
+        dir.path().join(file_name).to_string_lossy().to_string()
+    }
+
+    #[test]
+    fn test_read_known_sentences() {
+        let dir = temp_dir_with(&[
+            ("first.txt", "I am known.\n  Me too!  \n\n"),
+            ("second.txt", "I am known.\nAnother one."),
+        ]);
+
+        let known_sentences = read_known_sentences(&[path(&dir, "first.txt"), path(&dir, "second.txt")]).unwrap();
+        assert_eq!(known_sentences.len(), 3);
+        assert!(known_sentences.contains("Me too!"));
+        assert!(known_sentences.contains("Another one."));
+    }
+
+    #[test]
+    fn test_read_known_sentences_missing_file() {
+        assert!(read_known_sentences(&[String::from("/does/not/exist.txt")]).is_err());
+    }
+
+    #[test]
+    fn test_open_known_sentences_writer_appends() {
+        let dir = temp_dir_with(&[("known.txt", "I am known.")]);
+        let path = path(&dir, "known.txt");
+
+        let mut writer = open_known_sentences_writer(&path).unwrap();
+        writeln!(writer, "I am new.").unwrap();
+        writer.flush().unwrap();
+
+        assert_eq!(fs::read_to_string(&path).unwrap(), "I am known.\nI am new.\n");
+    }
+}
@@ -0,0 +1,118 @@
+use crate::checker::{self, CheckResult};
+use crate::extractor::split_sentences;
+use crate::replacer;
+use crate::rules::{load_rules, load_rules_file, CompiledRules, Rules};
+use crate::segmenter::{segmenter_for, Segmenter};
+
+// The compiled rules of a language together with its segmenter, to process texts
+// the same way an extraction does without going through the files of a loader.
+pub struct LanguageRules {
+    language: String,
+    rules: CompiledRules,
+    segmenter: Box<dyn Segmenter>,
+}
+
+impl LanguageRules {
+    // Uses the given rules. Files the segmenter needs, such as punkt models or SRX files,
+    // are read from the rules directory, see `load_rules` for how `None` is resolved.
+    pub fn new(language: &str, rules: &Rules, rules_dir: Option<&str>) -> Result<LanguageRules, String> {
+        LanguageRules::from_compiled(language, rules.compile()?, rules_dir)
+    }
+
+    // Loads the rules of the language, see `load_rules`
+    pub fn load(language: &str, rules_dir: Option<&str>) -> Result<LanguageRules, String> {
+        LanguageRules::from_compiled(language, load_rules(language, rules_dir)?, rules_dir)
+    }
+
+    // Loads the rules from a file outside of the rules directory, see `load_rules_file`
+    pub fn load_file(path: &str, language: &str, rules_dir: Option<&str>) -> Result<LanguageRules, String> {
+        LanguageRules::from_compiled(language, load_rules_file(path, language, rules_dir)?, rules_dir)
+    }
+
+    fn from_compiled(language: &str, rules: CompiledRules, rules_dir: Option<&str>) -> Result<LanguageRules, String> {
+        Ok(LanguageRules {
+            language: language.to_string(),
+            segmenter: segmenter_for(&rules, language, rules_dir)?,
+            rules,
+        })
+    }
+
+    pub fn language(&self) -> &str {
+        &self.language
+    }
+
+    pub fn rules(&self) -> &CompiledRules {
+        &self.rules
+    }
+
+    pub fn replace(&self, text: &str) -> String {
+        replacer::replace_strings(&self.rules, text)
+    }
+
+    pub fn check(&self, sentence: &str) -> CheckResult {
+        checker::check(&self.rules, sentence)
+    }
+
+    // Applies the replacements and splits the text into sentences
+    pub fn split(&self, text: &str) -> Vec<String> {
+        split_sentences(&self.rules, text, self.segmenter.as_ref())
+    }
+
+    // Splits the text and checks every sentence, without picking any of them
+    pub fn sentences(&self, text: &str) -> Vec<(String, CheckResult)> {
+        self.split(text).into_iter()
+            .map(|sentence| {
+                let result = self.check(&sentence);
+                (sentence.trim().to_string(), result)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::checker::Rejection;
+    use crate::test_helpers::temp_dir_with;
+
+    #[test]
+    fn test_language_rules() {
+        let rules = Rules {
+            max_word_count: 3,
+            replacements: vec![(String::from("Mr."), String::from("Mister"))],
+            ..Default::default()
+        };
+
+        let language_rules = LanguageRules::new("en", &rules, None).unwrap();
+        assert_eq!(language_rules.language(), "en");
+        assert_eq!(language_rules.replace("Hello Mr. Smith"), "Hello Mister Smith");
+        assert_eq!(language_rules.check("Hello Mister Smith"), Ok(()));
+        assert_eq!(language_rules.check("This is too long"), Err(Rejection::MaxWordCount));
+        assert_eq!(language_rules.sentences("Hello Mr. Smith"), vec![(String::from("Hello Mister Smith"), Ok(()))]);
+    }
+
+    #[test]
+    fn test_language_rules_rules_dir() {
+        let rules = Rules {
+            segmenter: String::from("srx"),
+            srx_file: String::from("srx/xx.srx"),
+            ..Default::default()
+        };
+        let srx = r#"<srx><body><languagerules><languagerule languagerulename="Default"><rule break="yes">
+            <beforebreak>\.</beforebreak><afterbreak>\s</afterbreak>
+            </rule></languagerule></languagerules>
+            <maprules><languagemap languagepattern=".*" languagerulename="Default"/></maprules></body></srx>"#;
+        let rules_dir = temp_dir_with(&[("srx/xx.srx", srx)]);
+
+        let language_rules = LanguageRules::new("xx", &rules, rules_dir.path().to_str()).unwrap();
+        assert_eq!(language_rules.split("One. Two."), vec!["One.", "Two."]);
+        assert!(LanguageRules::new("xx", &rules, Some("/does/not/exist")).is_err());
+    }
+
+    #[test]
+    fn test_language_rules_load() {
+        let language_rules = LanguageRules::load("en", None).unwrap();
+
+        assert_eq!(language_rules.rules().max_word_count, 14);
+    }
+}
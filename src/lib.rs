@@ -8,10 +8,35 @@ extern crate serde_json;
 extern crate toml;
 
 pub mod app;
+mod explain;
 mod extractor;
+mod filter;
+mod language;
 mod checker;
 mod replacer;
 mod rules;
+mod rules_diff;
+mod rule_tests;
 mod config;
+mod dedup;
+mod known_sentences;
 mod loaders;
+mod output;
 mod segmenter;
+mod stats;
+mod tokenizer;
+mod train_segmenter;
+mod validate;
+
+#[cfg(test)]
+mod test_helpers;
+
+// The API for using the rules and the extraction from other Rust code
+pub use checker::{check, CheckResult, Rejection};
+pub use config::Config;
+pub use extractor::{extract, extract_with, ExtractOptions};
+pub use language::LanguageRules;
+pub use loaders::{File, Loader, MediaWikiDump, Text, Wikipedia};
+pub use output::{OutputFormat, OutputOptions, SentenceSource};
+pub use replacer::replace_strings;
+pub use rules::{load_rules, load_rules_file, CompiledRules, Expectation, RuleTest, Rules};
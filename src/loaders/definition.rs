@@ -3,21 +3,50 @@ use std::path::Path;
 use crate::config::Config;
 
 // Each loader can process the files given by file_name as they need to.
-// The result should always be a Result<Vec<String>, String>:
-//   * A list of Strings, as extracted from the files - this can either be
+// The result should always be a Result<Vec<Text>, String>:
+//   * A list of Texts, as extracted from the files - the content can either be
 //     a full paragraph or text of sentences, or simply just one sentence
 //     If it's one sentence per entry, rust-punkt will still be used to try to split
 //     it and it can't be circumvented. In most cases we will have a text with
 //     multiple sentences in it, and it's totally fine - and probably more performant
 //     if that is returned as is.
+//   * If the source knows about them, the title and id of the article the content
+//     belongs to. These are used to report where a sentence came from.
 //   * Wrapped in a Result type
+// Loaders for files too large to keep in memory, such as MediaWiki dumps, also implement
+// `load_chunks` to pass the texts on while the file is still being read.
 // Loaders should preferably be light-weight. If fetching of these sentences is involved,
 // we recommend to write a script that can be used before running this scraper. This is
 // for example done for the Wikipedia extractor where we use WikiExtractor to get the
 // content into shape before running the loader here. This also allows to use existing
 // tooling for popular sources and we do not need to reinvent the wheel in this project.
 
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Text {
+  pub content: String,
+  pub title: Option<String>,
+  pub id: Option<String>,
+}
+
+impl Text {
+  pub fn new(content: String) -> Self {
+    Self { content, ..Default::default() }
+  }
+}
+
 pub trait Loader {
+  fn get_name(&self) -> &'static str;
   fn get_config(&self) -> &Config;
-  fn load(&self, file_name: &Path, filtered_titles: &HashSet<String>) -> Result<Vec<String>, String>;
+  fn load(&self, file_name: &Path, filtered_titles: &HashSet<String>) -> Result<Vec<Text>, String>;
+
+  // Passes the texts of the file to `on_texts` in chunks. By default all texts `load`
+  // returns are passed on at once.
+  fn load_chunks(
+    &self,
+    file_name: &Path,
+    filtered_titles: &HashSet<String>,
+    on_texts: &mut dyn FnMut(Vec<Text>) -> Result<(), String>,
+  ) -> Result<(), String> {
+    on_texts(self.load(file_name, filtered_titles)?)
+  }
 }
@@ -3,7 +3,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use super::definition::Loader;
+use super::definition::{Loader, Text};
 use crate::config::Config;
 
 pub struct FileLoader {
@@ -24,18 +24,22 @@ impl FileLoader {
 }
 
 impl Loader for FileLoader {
+  fn get_name(&self) -> &'static str {
+      "file"
+  }
+
   fn get_config(&self) -> &Config {
       &self.config
   }
 
-  fn load(&self, file_name: &Path, _filtered_titles: &HashSet<String>) -> Result<Vec<String>, String> {
+  fn load(&self, file_name: &Path, _filtered_titles: &HashSet<String>) -> Result<Vec<Text>, String> {
     let mut file = File::open(file_name).map_err(|e| format!("{}", e))?;
     let mut all_sentences = String::new();
     file.read_to_string(&mut all_sentences)
         .map_err(|e| format!("{}", e))?;
     Ok(all_sentences
         .lines()
-        .map(String::from)
+        .map(|line| Text::new(String::from(line)))
         .collect())
   }
 }
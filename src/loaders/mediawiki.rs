@@ -0,0 +1,268 @@
+use bzip2::read::MultiBzDecoder;
+use quick_xml::events::Event;
+use quick_xml::Reader;
+use std::collections::HashSet;
+use std::fs::File;
+use std::io::{BufRead, BufReader};
+use std::path::Path;
+
+use super::definition::{Loader, Text};
+use super::wikitext::strip_wikitext;
+use crate::config::Config;
+
+// Number of articles passed on at once while reading a dump
+const PAGES_PER_CHUNK: usize = 1000;
+
+// Reads MediaWiki XML dumps (pages-articles or pages-articles-multistream) directly,
+// either compressed as .bz2 or already decompressed. This makes the WikiExtractor
+// preprocessing step unnecessary, we strip the wikitext markup ourselves.
+// Dumps are streamed, only a chunk of articles is held in memory at a time.
+pub struct MediaWikiDump {
+  pub config: Config,
+}
+
+impl MediaWikiDump {
+  pub fn new(language: String, directory: String) -> Self {
+    let config = Config {
+      language,
+      directory,
+      max_sentences_per_text: 3,
+      file_prefix: String::from(""),
+    };
+
+    Self { config }
+  }
+}
+
+#[derive(Default)]
+struct Page {
+  title: String,
+  id: String,
+  namespace: String,
+  is_redirect: bool,
+  has_revision: bool,
+  text: String,
+}
+
+impl Loader for MediaWikiDump {
+  fn get_name(&self) -> &'static str {
+    "mediawiki-dump"
+  }
+
+  fn get_config(&self) -> &Config {
+    &self.config
+  }
+
+  fn load(&self, file_name: &Path, filtered_titles: &HashSet<String>) -> Result<Vec<Text>, String> {
+    let mut texts = vec![];
+    self.load_chunks(file_name, filtered_titles, &mut |chunk| {
+      texts.extend(chunk);
+      Ok(())
+    })?;
+
+    Ok(texts)
+  }
+
+  fn load_chunks(
+    &self,
+    file_name: &Path,
+    filtered_titles: &HashSet<String>,
+    on_texts: &mut dyn FnMut(Vec<Text>) -> Result<(), String>,
+  ) -> Result<(), String> {
+    let name = file_name.to_string_lossy();
+    if !file_name.is_file() || !name.contains(".xml") {
+      eprintln!("Skipping {:?} as it is not a MediaWiki XML dump", name);
+      return Ok(());
+    }
+
+    let file = File::open(file_name).map_err(|e| format!("{}", e))?;
+    if name.ends_with(".bz2") {
+      read_pages(BufReader::new(MultiBzDecoder::new(file)), filtered_titles, PAGES_PER_CHUNK, on_texts)
+    } else {
+      read_pages(BufReader::new(file), filtered_titles, PAGES_PER_CHUNK, on_texts)
+    }
+  }
+}
+
+fn read_pages(
+  input: impl BufRead,
+  filtered_titles: &HashSet<String>,
+  pages_per_chunk: usize,
+  on_texts: &mut dyn FnMut(Vec<Text>) -> Result<(), String>,
+) -> Result<(), String> {
+  let mut reader = Reader::from_reader(input);
+  let mut buffer = Vec::new();
+  let mut texts = Vec::new();
+  let mut page = Page::default();
+  let mut current_tag = Vec::new();
+
+  loop {
+    match reader.read_event_into(&mut buffer).map_err(|e| format!("invalid xml: {}", e))? {
+      Event::Start(element) => {
+        match element.name().as_ref() {
+          b"page" => page = Page::default(),
+          b"revision" => page.has_revision = true,
+          _ => {},
+        }
+        current_tag = element.name().as_ref().to_vec();
+      },
+      Event::Empty(element) => {
+        if element.name().as_ref() == b"redirect" {
+          page.is_redirect = true;
+        }
+      },
+      Event::Text(content) => {
+        let value = content.unescape().map_err(|e| format!("invalid xml: {}", e))?;
+        match current_tag.as_slice() {
+          b"title" => page.title.push_str(&value),
+          b"ns" => page.namespace.push_str(&value),
+          // Revisions have their own id, we only want the one of the page
+          b"id" if !page.has_revision => page.id.push_str(&value),
+          b"text" => page.text.push_str(&value),
+          _ => {},
+        }
+      },
+      Event::End(element) => {
+        if element.name().as_ref() == b"page" {
+          if let Some(text) = page_to_text(&page, filtered_titles) {
+            texts.push(text);
+          }
+          if texts.len() >= pages_per_chunk {
+            on_texts(std::mem::take(&mut texts))?;
+          }
+        }
+        current_tag.clear();
+      },
+      Event::Eof => break,
+      _ => {},
+    }
+    buffer.clear();
+  }
+
+  if !texts.is_empty() {
+    on_texts(texts)?;
+  }
+
+  Ok(())
+}
+
+// We only want actual articles, which live in the main namespace. The returned text
+// follows the WikiExtractor format: the title, followed by two line breaks and the content.
+fn page_to_text(page: &Page, filtered_titles: &HashSet<String>) -> Option<Text> {
+  if page.is_redirect || page.namespace.trim() != "0" {
+    return None;
+  }
+
+  let title = page.title.trim();
+  if !filtered_titles.is_empty() {
+    if !filtered_titles.contains(title) {
+      return None;
+    }
+    eprintln!("Article found in filter list: {:?}", title);
+  }
+
+  Some(Text {
+    content: format!("{}\n\n{}", title, strip_wikitext(&page.text)),
+    title: Some(title.to_string()),
+    id: Some(page.id.trim().to_string()),
+  })
+}
+
+#[cfg(test)]
+mod test {
+  use super::*;
+
+  const DUMP: &str = r#"<mediawiki>
+  <page>
+    <title>Foo</title>
+    <ns>0</ns>
+    <id>1</id>
+    <revision>
+      <id>100</id>
+      <text xml:space="preserve">'''Foo''' is a village in [[Bar]].&lt;ref&gt;Source&lt;/ref&gt;</text>
+    </revision>
+  </page>
+  <page>
+    <title>Talk:Foo</title>
+    <ns>1</ns>
+    <id>2</id>
+    <revision>
+      <text xml:space="preserve">Discussion.</text>
+    </revision>
+  </page>
+  <page>
+    <title>Fooo</title>
+    <ns>0</ns>
+    <id>3</id>
+    <redirect title="Foo" />
+    <revision>
+      <text xml:space="preserve">#REDIRECT [[Foo]]</text>
+    </revision>
+  </page>
+  <page>
+    <title>
+      Baz
+    </title>
+    <ns>0</ns>
+    <id>4</id>
+    <revision>
+      <text xml:space="preserve">Baz is a river.</text>
+    </revision>
+  </page>
+</mediawiki>"#;
+
+  fn read_chunks(filtered_titles: &HashSet<String>, pages_per_chunk: usize) -> Vec<Vec<Text>> {
+    let mut chunks = vec![];
+    read_pages(DUMP.as_bytes(), filtered_titles, pages_per_chunk, &mut |texts| {
+      chunks.push(texts);
+      Ok(())
+    }).unwrap();
+
+    chunks
+  }
+
+  #[test]
+  fn test_read_pages() {
+    let texts = read_chunks(&HashSet::new(), PAGES_PER_CHUNK).concat();
+
+    assert_eq!(texts, vec![
+      Text {
+        content: String::from("Foo\n\nFoo is a village in Bar."),
+        title: Some(String::from("Foo")),
+        id: Some(String::from("1")),
+      },
+      Text {
+        content: String::from("Baz\n\nBaz is a river."),
+        title: Some(String::from("Baz")),
+        id: Some(String::from("4")),
+      },
+    ]);
+  }
+
+  #[test]
+  fn test_read_pages_in_chunks() {
+    let chunks = read_chunks(&HashSet::new(), 1);
+
+    assert_eq!(chunks.len(), 2);
+    assert_eq!(chunks[0][0].title.as_deref(), Some("Foo"));
+    assert_eq!(chunks[1][0].title.as_deref(), Some("Baz"));
+  }
+
+  #[test]
+  fn test_read_pages_filtered_titles() {
+    let mut filtered_titles = HashSet::new();
+    filtered_titles.insert(String::from("Other"));
+
+    assert_eq!(read_chunks(&filtered_titles, PAGES_PER_CHUNK).concat().len(), 0);
+  }
+
+  #[test]
+  fn test_read_pages_filtered_titles_with_whitespace() {
+    let mut filtered_titles = HashSet::new();
+    filtered_titles.insert(String::from("Baz"));
+
+    let texts = read_chunks(&filtered_titles, PAGES_PER_CHUNK).concat();
+    assert_eq!(texts.len(), 1);
+    assert_eq!(texts[0].title.as_deref(), Some("Baz"));
+  }
+}
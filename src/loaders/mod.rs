@@ -1,7 +1,10 @@
 pub use wikipedia::Wikipedia;
 pub use file::FileLoader as File;
-pub use definition::Loader;
+pub use mediawiki::MediaWikiDump;
+pub use definition::{Loader, Text};
 
 pub mod wikipedia;
 pub mod file;
-mod definition;
\ No newline at end of file
+pub mod mediawiki;
+mod definition;
+mod wikitext;
\ No newline at end of file
@@ -4,7 +4,7 @@ use std::fs::File;
 use std::io::Read;
 use std::path::Path;
 
-use super::definition::Loader;
+use super::definition::{Loader, Text};
 use crate::config::Config;
 
 pub struct Wikipedia {
@@ -25,11 +25,15 @@ impl Wikipedia {
 }
 
 impl Loader for Wikipedia {
+  fn get_name(&self) -> &'static str {
+      "wikipedia"
+  }
+
   fn get_config(&self) -> &Config {
       &self.config
   }
 
-  fn load(&self, file_name: &Path, filtered_titles: &HashSet<String>) -> Result<Vec<String>, String> {
+  fn load(&self, file_name: &Path, filtered_titles: &HashSet<String>) -> Result<Vec<Text>, String> {
     let mut file = File::open(file_name).map_err(|e| format!("{}", e))?;
     let mut json_str = String::new();
     file.read_to_string(&mut json_str)
@@ -40,11 +44,15 @@ impl Loader for Wikipedia {
             serde_json::from_str(line)
                 .map_err(|e| format!("invalid json: {}", e))
                 .map(|mut article: Value| {
-                  let text = article["text"]
-                    .take()
-                    .as_str()
-                    .map(String::from)
-                    .unwrap_or_default();
+                  let text = Text {
+                    content: article["text"]
+                      .take()
+                      .as_str()
+                      .map(String::from)
+                      .unwrap_or_default(),
+                    title: article["title"].as_str().map(String::from),
+                    id: article["id"].as_str().map(String::from),
+                  };
 
                   if filtered_titles.is_empty() {
                     return text
@@ -57,7 +65,7 @@ impl Loader for Wikipedia {
                       eprintln!("Article found in filter list: {:?}", trimmed_title);
                       text
                     },
-                    None => String::new(),
+                    None => Text::default(),
                   }
                 })
                 .unwrap_or_default()
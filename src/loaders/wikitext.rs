@@ -0,0 +1,198 @@
+use lazy_static::lazy_static;
+use regex::Regex;
+
+// Tags whose content is never prose we could read out loud, they get removed
+// together with everything inside them.
+const DROPPED_TAGS: [&str; 13] = [
+    "ref", "gallery", "math", "chem", "code", "pre", "syntaxhighlight",
+    "source", "timeline", "score", "hiero", "imagemap", "graph",
+];
+
+lazy_static! {
+    static ref COMMENT: Regex = Regex::new(r"(?s)<!--.*?-->").unwrap();
+    static ref SELF_CLOSING_REF: Regex = Regex::new(r"(?i)<ref[^>]*/>").unwrap();
+    static ref DROPPED_BLOCKS: Vec<Regex> = DROPPED_TAGS.iter().map(|tag| {
+        Regex::new(&format!(r"(?is)<{}(\s[^>]*)?>.*?</{}\s*>", tag, tag)).unwrap()
+    }).collect();
+    static ref INNER_LINK: Regex = Regex::new(r"\[\[([^\[\]]*)\]\]").unwrap();
+    static ref EXTERNAL_LINK_WITH_LABEL: Regex = Regex::new(r"\[(?:https?:)?//[^\s\]]+\s+([^\]]*)\]").unwrap();
+    static ref EXTERNAL_LINK: Regex = Regex::new(r"\[(?:https?:)?//[^\s\]]*\]").unwrap();
+    static ref EMPHASIS: Regex = Regex::new(r"'{2,5}").unwrap();
+    static ref HTML_TAG: Regex = Regex::new(r"</?[a-zA-Z][^>]*>").unwrap();
+    static ref BEHAVIOR_SWITCH: Regex = Regex::new(r"__[A-Z]+__").unwrap();
+    static ref MULTIPLE_SPACES: Regex = Regex::new(r"[ \t]{2,}").unwrap();
+    static ref SPACE_BEFORE_PUNCTUATION: Regex = Regex::new(r" ([.,;:!?])").unwrap();
+}
+
+// Turns raw MediaWiki markup into plain text paragraphs, roughly matching what
+// WikiExtractor produces. We do not try to render templates, tables or media,
+// these are dropped completely. Links are replaced by their label, headings,
+// lists and leftover table rows are skipped. Each paragraph ends up on its own line.
+pub fn strip_wikitext(raw: &str) -> String {
+    let mut text = COMMENT.replace_all(raw, "").to_string();
+    text = SELF_CLOSING_REF.replace_all(&text, "").to_string();
+    for regex in DROPPED_BLOCKS.iter() {
+        text = regex.replace_all(&text, "").to_string();
+    }
+
+    text = remove_nested(&text, "{{", "}}");
+    text = remove_nested(&text, "{|", "|}");
+    text = replace_links(&text);
+    text = EXTERNAL_LINK_WITH_LABEL.replace_all(&text, "$1").to_string();
+    text = EXTERNAL_LINK.replace_all(&text, "").to_string();
+    text = EMPHASIS.replace_all(&text, "").to_string();
+    text = HTML_TAG.replace_all(&text, "").to_string();
+    text = BEHAVIOR_SWITCH.replace_all(&text, "").to_string();
+    text = decode_entities(&text);
+
+    text.lines()
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty() && !is_markup_line(line))
+        .map(|line| {
+            let collapsed = MULTIPLE_SPACES.replace_all(line, " ");
+            SPACE_BEFORE_PUNCTUATION.replace_all(&collapsed, "$1").to_string()
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+// Headings, lists, indentations and remains of tables do not contain
+// full sentences, so we skip those lines completely.
+fn is_markup_line(line: &str) -> bool {
+    line.starts_with(|c: char| "=*#:;|!{}".contains(c))
+}
+
+// Removes everything between (possibly nested) opening and closing markers,
+// for example templates `{{...}}` or tables `{|...|}`. Unbalanced openings
+// remove the rest of the text, as we can't tell where they were supposed to end.
+fn remove_nested(text: &str, open: &str, close: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut depth = 0;
+    let mut rest = text;
+    while !rest.is_empty() {
+        if rest.starts_with(open) {
+            depth += 1;
+            rest = &rest[open.len()..];
+        } else if depth > 0 && rest.starts_with(close) {
+            depth -= 1;
+            rest = &rest[close.len()..];
+        } else {
+            let c = rest.chars().next().unwrap();
+            if depth == 0 {
+                result.push(c);
+            }
+            rest = &rest[c.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+// Replaces internal links with their label, starting with the innermost link so
+// that captions inside of file links are handled before the file link itself.
+// Links into other namespaces (files, categories, interwiki links) are removed.
+fn replace_links(text: &str) -> String {
+    let mut result = text.to_string();
+    loop {
+        let replaced = INNER_LINK.replace_all(&result, |captures: &regex::Captures| {
+            let link = &captures[1];
+            let target = link.split('|').next().unwrap_or_default().trim();
+            if is_namespaced(target) {
+                return String::new();
+            }
+
+            let label = link.rsplit('|').next().unwrap_or_default().trim();
+            if label.is_empty() {
+                target.trim_start_matches(':').to_string()
+            } else {
+                label.to_string()
+            }
+        }).to_string();
+
+        if replaced == result {
+            return result;
+        }
+        result = replaced;
+    }
+}
+
+fn is_namespaced(target: &str) -> bool {
+    match target.split_once(':') {
+        Some((prefix, _)) => !prefix.is_empty() && !prefix.contains(char::is_whitespace),
+        None => false,
+    }
+}
+
+fn decode_entities(text: &str) -> String {
+    text.replace("&nbsp;", " ")
+        .replace("&ndash;", "–")
+        .replace("&mdash;", "—")
+        .replace("&quot;", "\"")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&amp;", "&")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_plain_text() {
+        assert_eq!(strip_wikitext("This is plain text."), "This is plain text.");
+    }
+
+    #[test]
+    fn test_templates_nested() {
+        assert_eq!(strip_wikitext("{{Infobox|name={{lang|de|Foo}}}}Foo is a village."), "Foo is a village.");
+    }
+
+    #[test]
+    fn test_refs_and_comments() {
+        assert_eq!(
+            strip_wikitext("Foo is a village<ref name=\"a\">Some source</ref><ref name=\"b\" /> in Bar.<!-- hidden -->"),
+            "Foo is a village in Bar."
+        );
+    }
+
+    #[test]
+    fn test_tables() {
+        assert_eq!(strip_wikitext("Before.\n{| class=\"wikitable\"\n|-\n| Cell\n|}\nAfter."), "Before.\nAfter.");
+    }
+
+    #[test]
+    fn test_links() {
+        assert_eq!(strip_wikitext("Foo lies in [[Bar]] near the [[River Baz|river]]."), "Foo lies in Bar near the river.");
+    }
+
+    #[test]
+    fn test_namespaced_links() {
+        assert_eq!(
+            strip_wikitext("[[File:Foo.jpg|thumb|A [[village]] in Bar]]Foo is a village.[[Category:Villages]]"),
+            "Foo is a village."
+        );
+    }
+
+    #[test]
+    fn test_external_links() {
+        assert_eq!(strip_wikitext("See the [https://example.com official site] and [https://example.com]."), "See the official site and.");
+    }
+
+    #[test]
+    fn test_emphasis_and_tags() {
+        assert_eq!(strip_wikitext("'''Foo''' is a ''small''<br/> village."), "Foo is a small village.");
+    }
+
+    #[test]
+    fn test_markup_lines() {
+        assert_eq!(
+            strip_wikitext("Intro text.\n\n== History ==\n* A list item\n# Another one\n: Indented\nMore text."),
+            "Intro text.\nMore text."
+        );
+    }
+
+    #[test]
+    fn test_entities() {
+        assert_eq!(strip_wikitext("Foo&nbsp;Bar &amp; Baz"), "Foo Bar & Baz");
+    }
+}
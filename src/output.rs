@@ -0,0 +1,156 @@
+use clap::ValueEnum;
+use serde::Serialize;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// One sentence per line
+    #[default]
+    Text,
+    /// One JSON object per line, including where the sentence came from
+    Jsonl,
+    /// Tab separated, ready to be imported as Common Voice bulk submission
+    CvTsv,
+}
+
+#[derive(Clone, Debug, Default)]
+pub struct OutputOptions {
+    pub format: OutputFormat,
+    // Name of the source used for the Common Voice export, defaults to the loader name
+    pub source_name: Option<String>,
+    pub domain: String,
+}
+
+// Where an accepted sentence came from. The index is the position of the sentence
+// within the segmented article text, which allows to trace it back to the source.
+#[derive(Debug, Serialize)]
+pub struct SentenceSource<'a> {
+    pub loader: &'a str,
+    pub file: &'a str,
+    pub title: Option<&'a str>,
+    pub id: Option<&'a str>,
+    pub index: usize,
+}
+
+#[derive(Serialize)]
+struct JsonRecord<'a> {
+    sentence: &'a str,
+    #[serde(flatten)]
+    source: &'a SentenceSource<'a>,
+}
+
+impl OutputOptions {
+    pub fn header(&self) -> Option<&'static str> {
+        match self.format {
+            OutputFormat::CvTsv => Some("sentence\tsource\tdomain\tlocale"),
+            _ => None,
+        }
+    }
+
+    pub fn format_sentence(&self, sentence: &str, source: &SentenceSource, locale: &str) -> String {
+        match self.format {
+            OutputFormat::Text => sentence.to_string(),
+            OutputFormat::Jsonl => {
+                serde_json::to_string(&JsonRecord { sentence, source }).unwrap()
+            },
+            OutputFormat::CvTsv => {
+                format!(
+                    "{}\t{}\t{}\t{}",
+                    without_tabs(sentence),
+                    without_tabs(&self.cv_source(source)),
+                    without_tabs(&self.domain),
+                    locale,
+                )
+            },
+        }
+    }
+
+    // The source column for the Common Voice import, for example "Wikipedia: Some article"
+    fn cv_source(&self, source: &SentenceSource) -> String {
+        let name = self.source_name.as_deref().unwrap_or(source.loader);
+        match source.title {
+            Some(title) if !title.is_empty() => format!("{}: {}", name, title),
+            _ => name.to_string(),
+        }
+    }
+}
+
+fn without_tabs(value: &str) -> String {
+    value.replace('\t', " ")
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn source() -> SentenceSource<'static> {
+        SentenceSource {
+            loader: "wikipedia",
+            file: "AA/wiki_00",
+            title: Some("Foo"),
+            id: Some("12"),
+            index: 3,
+        }
+    }
+
+    fn options(format: OutputFormat) -> OutputOptions {
+        OutputOptions {
+            format,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn test_format_text() {
+        assert_eq!(options(OutputFormat::Text).format_sentence("Foo is a village.", &source(), "en"), "Foo is a village.");
+    }
+
+    #[test]
+    fn test_format_jsonl() {
+        assert_eq!(
+            options(OutputFormat::Jsonl).format_sentence("Foo is a village.", &source(), "en"),
+            r#"{"sentence":"Foo is a village.","loader":"wikipedia","file":"AA/wiki_00","title":"Foo","id":"12","index":3}"#
+        );
+    }
+
+    #[test]
+    fn test_format_jsonl_without_article() {
+        let source = SentenceSource {
+            title: None,
+            id: None,
+            ..source()
+        };
+
+        assert_eq!(
+            options(OutputFormat::Jsonl).format_sentence("Foo", &source, "en"),
+            r#"{"sentence":"Foo","loader":"wikipedia","file":"AA/wiki_00","title":null,"id":null,"index":3}"#
+        );
+    }
+
+    #[test]
+    fn test_header() {
+        assert_eq!(options(OutputFormat::Text).header(), None);
+        assert_eq!(options(OutputFormat::Jsonl).header(), None);
+        assert_eq!(options(OutputFormat::CvTsv).header(), Some("sentence\tsource\tdomain\tlocale"));
+    }
+
+    #[test]
+    fn test_format_cv_tsv() {
+        let options = OutputOptions {
+            format: OutputFormat::CvTsv,
+            source_name: Some(String::from("Wikipedia")),
+            domain: String::from("general"),
+        };
+
+        assert_eq!(options.format_sentence("Foo is a\tvillage.", &source(), "en"), "Foo is a village.\tWikipedia: Foo\tgeneral\ten");
+    }
+
+    #[test]
+    fn test_format_cv_tsv_defaults_to_loader() {
+        let source = SentenceSource {
+            title: None,
+            ..source()
+        };
+
+        assert_eq!(options(OutputFormat::CvTsv).format_sentence("Foo", &source, "de"), "Foo\twikipedia\t\tde");
+    }
+}
@@ -1,31 +1,20 @@
-use crate::rules::Rules;
-use toml::Value;
-use regex::Regex;
+use crate::rules::CompiledRules;
 
-pub fn replace_strings(rules: &Rules, raw: &str) -> String {
+pub fn replace_strings(rules: &CompiledRules, raw: &str) -> String {
     let mut result = raw.trim().to_string();
 
     // bracket removal
-    for bracket_pair in rules.remove_brackets_list.iter() {
-        if Value::as_array(bracket_pair).unwrap().len() == 2 {
-            let br0 = bracket_pair[0].as_str().unwrap();
-            let br1 = bracket_pair[1].as_str().unwrap();
-            let regex = Regex::new(format!(r#"\{}[^\{}\{}]*\{}"#, br0, br0, br1, br1).as_str()).unwrap();
-            let mut prev = String::from("");
-            while prev != result {
-                prev = result.clone();
-                result = regex.replace_all(prev.as_str(), " ").to_string().replace("  ", " ");
-            }
+    for regex in rules.remove_brackets.iter() {
+        let mut prev = String::from("");
+        while prev != result {
+            prev = result.clone();
+            result = regex.replace_all(prev.as_str(), " ").to_string().replace("  ", " ");
         }
     }
 
     // replacements
-    for replacement_rules in rules.replacements.iter() {
-        if Value::as_array(replacement_rules).unwrap().len() == 2 {
-            let abbreviation = replacement_rules[0].as_str().unwrap();
-            let replacement = replacement_rules[1].as_str().unwrap();
-            result = result.replace(abbreviation, replacement);
-        }
+    for (abbreviation, replacement) in rules.replacements.iter() {
+        result = result.replace(abbreviation.as_str(), replacement.as_str());
     }
 
     result
@@ -33,37 +22,16 @@ pub fn replace_strings(rules: &Rules, raw: &str) -> String {
 
 #[cfg(test)]
 mod test {
-    use super::*;
-    use toml::Value;
-    use toml::value::Array;
+    use crate::rules::Rules;
 
-    #[test]
-    fn test_nothing() {
-        let rules : Rules = Rules {
-            ..Default::default()
-        };
-
-        assert_eq!(replace_strings(&rules, &String::from("Me&You")), "Me&You");
+    // Shadows `replace_strings` to compile the rules for every call in the tests
+    fn replace_strings(rules: &Rules, raw: &str) -> String {
+        super::replace_strings(&rules.compile().unwrap(), raw)
     }
 
     #[test]
-    fn test_nothing_if_replacement_missing() {
-        let rules : Rules = Rules {
-            replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap()]).unwrap()
-            ],
-            ..Default::default()
-        };
-
-        assert_eq!(replace_strings(&rules, &String::from("Me&You")), "Me&You");
-    }
-
-    #[test]
-    fn test_nothing_if_empty_rules() {
+    fn test_nothing() {
         let rules : Rules = Rules {
-            replacements: vec![
-                Value::try_from::<Array>(vec![]).unwrap()
-            ],
             ..Default::default()
         };
 
@@ -74,7 +42,7 @@ mod test {
     fn test_one_abbreviation() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap(), Value::try_from("and").unwrap()]).unwrap()
+                (String::from("&"), String::from("and"))
             ],
             ..Default::default()
         };
@@ -86,7 +54,7 @@ mod test {
     fn test_one_abbreviation_whitespace() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from(" & ").unwrap(), Value::try_from(" and ").unwrap()]).unwrap()
+                (String::from(" & "), String::from(" and "))
             ],
             ..Default::default()
         };
@@ -98,7 +66,7 @@ mod test {
     fn test_one_abbreviation_mixed() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap(), Value::try_from(" and ").unwrap()]).unwrap()
+                (String::from("&"), String::from(" and "))
             ],
             ..Default::default()
         };
@@ -110,7 +78,7 @@ mod test {
     fn test_multiple_occurances() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap(), Value::try_from("and").unwrap()]).unwrap()
+                (String::from("&"), String::from("and"))
             ],
             ..Default::default()
         };
@@ -122,8 +90,8 @@ mod test {
     fn test_multiple_abbreviations() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap(), Value::try_from(" and ").unwrap()]).unwrap(),
-                Value::try_from([Value::try_from("etc.").unwrap(), Value::try_from("et cetera").unwrap()]).unwrap(),
+                (String::from("&"), String::from(" and ")),
+                (String::from("etc."), String::from("et cetera")),
             ],
             ..Default::default()
         };
@@ -136,7 +104,7 @@ mod test {
     fn test_replace_empty() {
         let rules : Rules = Rules {
             replacements: vec![
-                Value::try_from([Value::try_from("&").unwrap(), Value::try_from("").unwrap()]).unwrap(),
+                (String::from("&"), String::from("")),
             ],
             ..Default::default()
         };
@@ -157,8 +125,8 @@ mod test {
     fn test_remove_brackets_list() {
         let rules = Rules {
             remove_brackets_list: vec![
-                Value::try_from([Value::try_from("(").unwrap(), Value::try_from(")").unwrap()]).unwrap(),
-                Value::try_from([Value::try_from("[").unwrap(), Value::try_from("]").unwrap()]).unwrap(),
+                (String::from("("), String::from(")")),
+                (String::from("["), String::from("]")),
             ],
             ..Default::default()
         };
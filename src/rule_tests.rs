@@ -0,0 +1,124 @@
+use crate::checker::{check, CheckResult};
+use crate::replacer::replace_strings;
+use crate::rules::{load_rules, CompiledRules, Expectation, RuleTest};
+
+// A test case of a rules file that did not have the expected result
+#[derive(Debug, PartialEq, Eq)]
+pub struct TestFailure {
+    pub sentence: String,
+    pub expected: String,
+    pub actual: String,
+}
+
+fn describe(result: &CheckResult) -> String {
+    match result {
+        Ok(()) => String::from("accept"),
+        Err(rejection) => format!("reject by {}", rejection),
+    }
+}
+
+fn run_test(rules: &CompiledRules, test: &RuleTest) -> Option<TestFailure> {
+    // Replacements are part of the rules, so the sentence goes through them as well
+    let result = check(rules, &replace_strings(rules, &test.sentence));
+    let passed = match (&test.expected, &result, &test.rule) {
+        (Expectation::Accept, Ok(()), _) => true,
+        (Expectation::Reject, Err(rejection), Some(rule)) => rejection.rule() == rule,
+        (Expectation::Reject, Err(_), None) => true,
+        _ => false,
+    };
+    if passed {
+        return None;
+    }
+
+    let expected = match (&test.expected, &test.rule) {
+        (Expectation::Accept, _) => String::from("accept"),
+        (Expectation::Reject, Some(rule)) => format!("reject by {}", rule),
+        (Expectation::Reject, None) => String::from("reject"),
+    };
+    Some(TestFailure {
+        sentence: test.sentence.clone(),
+        expected,
+        actual: describe(&result),
+    })
+}
+
+pub fn run_rule_tests(rules: &CompiledRules) -> Vec<TestFailure> {
+    rules.tests.iter().filter_map(|test| run_test(rules, test)).collect()
+}
+
+pub fn test_rules(language: &str, rules_dir: Option<&str>) -> Result<(), String> {
+    let rules = load_rules(language, rules_dir)?;
+    let failures = run_rule_tests(&rules);
+    for failure in failures.iter() {
+        eprintln!("FAILED {:?}: expected {}, got {}", failure.sentence, failure.expected, failure.actual);
+    }
+
+    let total = rules.tests.len();
+    eprintln!("{} of {} rule tests passed", total - failures.len(), total);
+    if !failures.is_empty() {
+        return Err(format!("{} rule test(s) failed for {:?}", failures.len(), language));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rules;
+
+    fn rule_test(sentence: &str, expected: Expectation, rule: Option<&str>) -> RuleTest {
+        RuleTest {
+            sentence: String::from(sentence),
+            expected,
+            rule: rule.map(String::from),
+        }
+    }
+
+    #[test]
+    fn test_run_rule_tests() {
+        let rules = Rules {
+            max_word_count: 3,
+            replacements: vec![(String::from("Mr."), String::from("Mister"))],
+            tests: vec![
+                rule_test("Hello Mr. Smith", Expectation::Accept, None),
+                rule_test("This is too long", Expectation::Reject, Some("max_word_count")),
+                rule_test("This is too long", Expectation::Reject, None),
+            ],
+            ..Default::default()
+        }.compile().unwrap();
+
+        assert_eq!(run_rule_tests(&rules), vec![]);
+    }
+
+    #[test]
+    fn test_run_rule_tests_failures() {
+        let rules = Rules {
+            max_word_count: 3,
+            tests: vec![
+                rule_test("This is too long", Expectation::Accept, None),
+                rule_test("Fine", Expectation::Reject, None),
+                rule_test("This is too long", Expectation::Reject, Some("min_word_count")),
+            ],
+            ..Default::default()
+        }.compile().unwrap();
+
+        assert_eq!(run_rule_tests(&rules), vec![
+            TestFailure {
+                sentence: String::from("This is too long"),
+                expected: String::from("accept"),
+                actual: String::from("reject by max_word_count"),
+            },
+            TestFailure {
+                sentence: String::from("Fine"),
+                expected: String::from("reject"),
+                actual: String::from("accept"),
+            },
+            TestFailure {
+                sentence: String::from("This is too long"),
+                expected: String::from("reject by min_word_count"),
+                actual: String::from("reject by max_word_count"),
+            },
+        ]);
+    }
+}
@@ -1,40 +1,176 @@
-use serde::Deserialize;
-use std::fs::File;
-use std::io::Read;
-use toml::value::Array;
+use include_dir::{include_dir, Dir};
+use regex::Regex;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::ops::Deref;
 use std::collections::HashSet;
 use std::path::Path;
+use std::str::FromStr;
+use toml::{Table, Value};
+use crate::segmenter::CLOSING_CHARACTERS;
+use crate::tokenizer::DictionaryTokenizer;
 
-pub fn load_rules(language: &str) -> Rules {
-    let file_name = format!("./src/rules/{}.toml", language);
-    eprintln!("Loading rules at {:?}", file_name);
-    let mut file = File::open(file_name).map_err(|e| format!("{}", e)).unwrap();
-    let mut rules_str = String::new();
-    file.read_to_string(&mut rules_str)
-        .map_err(|e| format!("{}", e)).unwrap();
-    let mut rules: Rules = toml::from_str(&rules_str).unwrap();
+pub const DEFAULT_RULES_DIR: &str = "./src/rules";
+// Keys which are used to resolve the inherited rules and are not part of the rules
+pub const INHERITANCE_KEYS: [&str; 2] = ["extends", "append"];
+
+// The rules shipped with this repository, used for every file that can't be found
+// in the rules directory. This allows to run the binary from any directory.
+static EMBEDDED_RULES: Dir = include_dir!("$CARGO_MANIFEST_DIR/src/rules");
+
+pub struct RulesFile {
+    // Path of the file, or a description of the embedded file
+    pub name: String,
+    pub content: String,
+}
+
+// Reads a file relative to the rules directory, falling back to the embedded rules.
+pub fn read_rules_file(rules_dir: Option<&str>, file_name: &str) -> Result<Option<RulesFile>, String> {
+    let path = Path::new(rules_dir.unwrap_or(DEFAULT_RULES_DIR)).join(file_name);
+    if path.exists() {
+        let name = path.to_string_lossy().to_string();
+        let content = fs::read_to_string(&path).map_err(|e| format!("{}: {}", name, e))?;
+        return Ok(Some(RulesFile { name, content }));
+    }
+
+    Ok(EMBEDDED_RULES.get_file(file_name)
+        .and_then(|file| file.contents_utf8())
+        .map(|content| RulesFile {
+            name: format!("{} (embedded)", file_name),
+            content: content.to_string(),
+        }))
+}
+
+pub fn read_language_rules_file(rules_dir: Option<&str>, language: &str) -> Result<RulesFile, String> {
+    let file_name = format!("{}.toml", language);
+    read_rules_file(rules_dir, &file_name)?
+        .ok_or_else(|| format!("No rules found for language {:?}", language))
+}
+
+pub fn load_rules(language: &str, rules_dir: Option<&str>) -> Result<CompiledRules, String> {
+    let rules = resolve_rules(language, rules_dir)?;
     eprintln!("Using Rules {:?}", rules);
 
-    let disallowed_file_name = format!("./src/rules/disallowed_words/{}.txt", language);
-    let list_exists = Path::new(&disallowed_file_name).exists();
-    eprintln!("Using disallowed_word_file = {:?}", list_exists);
-    if list_exists {
-        let mut file = File::open(disallowed_file_name).map_err(|e| format!("{}", e)).unwrap();
-        let mut words_str = String::new();
-        file.read_to_string(&mut words_str)
-            .map_err(|e| format!("{}", e)).unwrap();
-        rules.disallowed_words.extend::<HashSet<String>>(
-            words_str
+    rules.compile().map_err(|e| format!("Rules of {:?}: {}", language, e))
+}
+
+// Loads the rules from a file outside of the rules directory, for example another
+// version of a rules file. Inherited rules and disallowed words of the language are
+// still taken from the rules directory.
+pub fn load_rules_file(path: &str, language: &str, rules_dir: Option<&str>) -> Result<CompiledRules, String> {
+    let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+    let rules_file = RulesFile { name: path.to_string(), content };
+    let table = resolve_rules_table(language, rules_file, rules_dir, &mut vec![])?;
+    let rules: Rules = Value::Table(table).try_into().map_err(|e| format!("{}: {}", path, e))?;
+
+    rules.compile().map_err(|e| format!("{}: {}", path, e))
+}
+
+// Reads the rules of the language, including all rules it inherits with `extends`
+pub fn resolve_rules(language: &str, rules_dir: Option<&str>) -> Result<Rules, String> {
+    let rules_file = read_language_rules_file(rules_dir, language)?;
+    let table = resolve_rules_table(language, rules_file, rules_dir, &mut vec![])?;
+    Value::Table(table).try_into().map_err(|e| format!("Rules of {:?}: {}", language, e))
+}
+
+// A rules file can extend the rules of another language with `extends = "pt"`. All
+// keys in the file replace the inherited value, lists in the `[append]` table get
+// appended to the inherited list instead. The disallowed words file of every
+// language in the chain is appended as well.
+fn resolve_rules_table(
+    language: &str,
+    rules_file: RulesFile,
+    rules_dir: Option<&str>,
+    chain: &mut Vec<String>,
+) -> Result<Table, String> {
+    // The words of the word list files are only added once all rules files are merged,
+    // otherwise a rules file setting `disallowed_words` or `dictionary_words` itself
+    // would drop the words of the files of the languages it extends
+    let mut word_lists = Table::new();
+    let mut rules = merge_rules_files(language, rules_file, rules_dir, chain, &mut word_lists)?;
+    for (key, words) in word_lists {
+        append_list(&mut rules, &key, words)?;
+    }
+
+    Ok(rules)
+}
+
+fn merge_rules_files(
+    language: &str,
+    rules_file: RulesFile,
+    rules_dir: Option<&str>,
+    chain: &mut Vec<String>,
+    word_lists: &mut Table,
+) -> Result<Table, String> {
+    if chain.iter().any(|extended| extended == language) {
+        chain.push(language.to_string());
+        return Err(format!("Rules extend each other in a loop: {}", chain.join(" -> ")));
+    }
+    chain.push(language.to_string());
+
+    eprintln!("Loading rules at {:?}", rules_file.name);
+    // Parsing into the rules first gives us error messages with the line of the error
+    toml::from_str::<Rules>(&rules_file.content).map_err(|e| format!("{}: {}", rules_file.name, e))?;
+    let mut table: Table = toml::from_str(&rules_file.content).map_err(|e| format!("{}: {}", rules_file.name, e))?;
+
+    let mut rules = match table.remove("extends") {
+        Some(Value::String(parent)) => {
+            let parent_file = read_language_rules_file(rules_dir, &parent)?;
+            merge_rules_files(&parent, parent_file, rules_dir, chain, word_lists)?
+        },
+        Some(other) => return Err(format!("{}: extends needs to be a language, got {}", rules_file.name, other)),
+        None => Table::new(),
+    };
+    let append = match table.remove("append") {
+        Some(Value::Table(append)) => append,
+        Some(other) => return Err(format!("{}: append needs to be a table, got {}", rules_file.name, other)),
+        None => Table::new(),
+    };
+
+    rules.extend(table);
+    for (key, value) in append {
+        append_list(&mut rules, &key, value).map_err(|e| format!("{}: {}", rules_file.name, e))?;
+    }
+
+    let disallowed_words_file = read_rules_file(rules_dir, &format!("disallowed_words/{}.txt", language))?;
+    eprintln!("Using disallowed_word_file = {:?}", disallowed_words_file.as_ref().map(|file| &file.name));
+    if let Some(disallowed_words_file) = disallowed_words_file {
+        let words = disallowed_words_file.content
             .split('\n')
-            .map(|s| s.trim().to_lowercase())
-            .collect()
-        );
+            .map(|s| Value::String(s.trim().to_lowercase()))
+            .collect();
+        append_list(word_lists, "disallowed_words", Value::Array(words))?;
+    }
+
+    let dictionary_words_file = read_rules_file(rules_dir, &format!("words/{}.txt", language))?;
+    if let Some(dictionary_words_file) = dictionary_words_file {
+        eprintln!("Using dictionary_words_file = {:?}", dictionary_words_file.name);
+        let words = dictionary_words_file.content
+            .lines()
+            .map(|s| s.trim())
+            .filter(|s| !s.is_empty())
+            .map(|s| Value::String(s.to_lowercase()))
+            .collect();
+        append_list(word_lists, "dictionary_words", Value::Array(words))?;
+    }
+
+    Ok(rules)
+}
+
+fn append_list(rules: &mut Table, key: &str, value: Value) -> Result<(), String> {
+    let values = match value {
+        Value::Array(values) => values,
+        other => return Err(format!("append.{} needs to be a list, got {}", key, other)),
+    };
+    match rules.entry(key).or_insert_with(|| Value::Array(vec![])) {
+        Value::Array(existing) => existing.extend(values),
+        other => return Err(format!("{} is not a list and can't be appended to, got {}", key, other)),
     }
 
-    rules
+    Ok(())
 }
 
-#[derive(Debug,Deserialize)]
+#[derive(Clone,Debug,Deserialize,Serialize)]
 #[serde(default)]
 pub struct Rules {
     pub segmenter: String,
@@ -48,17 +184,61 @@ pub struct Rules {
     pub needs_punctuation_end: bool,
     pub needs_uppercase_start: bool,
     pub needs_letter_start: bool,
-    pub remove_brackets_list: Array,
+    // Pairs of opening and closing brackets
+    pub remove_brackets_list: Vec<(String, String)>,
     pub allowed_symbols_regex: String,
-    pub disallowed_symbols: Array,
+    pub disallowed_symbols: Vec<String>,
     pub disallowed_words: HashSet<String>,
-    pub broken_whitespace: Array,
-    pub abbreviation_patterns: Array,
-    pub other_patterns: Array,
+    pub broken_whitespace: Vec<String>,
+    pub abbreviation_patterns: Vec<String>,
+    pub other_patterns: Vec<String>,
     pub stem_separator_regex: String,
-    pub replacements: Array,
-    pub even_symbols: Array,
-    pub matching_symbols: Array,
+    // Pairs of the text to search for and its replacement
+    pub replacements: Vec<(String, String)>,
+    pub even_symbols: Vec<String>,
+    pub matching_symbols: Vec<(String, String)>,
+    pub dedup_unicode_normalization: bool,
+    pub dedup_case_insensitive: bool,
+    pub dedup_ignore_punctuation: bool,
+    pub near_duplicate_threshold: f64,
+    pub near_duplicate_max_count: usize,
+    pub max_sentences_per_template: usize,
+    // Mask capitalized words other than the first one when building sentence templates
+    pub template_mask_capitalized: bool,
+    // Used by the "rules" segmenter: characters ending a sentence, abbreviations not
+    // ending a sentence, characters after the end which still belong to it, and words
+    // after which a number followed by a dot is an ordinal
+    pub segmenter_terminators: Vec<char>,
+    pub segmenter_abbreviations: Vec<String>,
+    pub segmenter_closing_characters: Vec<char>,
+    pub segmenter_ordinal_words: Vec<String>,
+    // Segmenter used instead of "python" when built without the python feature
+    pub segmenter_fallback: String,
+    // Used by the "srx" segmenter: SRX file relative to the rules directory
+    pub srx_file: String,
+    // How the word based rules split sentences into words: "" splits at whitespace,
+    // "dictionary" uses the dictionary_words for scripts without spaces
+    pub word_tokenizer: String,
+    pub dictionary_words: HashSet<String>,
+    // Example sentences to verify the rules with, see `test-rules`
+    pub tests: Vec<RuleTest>,
+}
+
+#[derive(Clone, Copy, Debug, Deserialize, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+pub enum Expectation {
+    Accept,
+    Reject,
+}
+
+// A sentence and whether the rules should accept or reject it. For rejected
+// sentences the rule which should reject it can be given as well.
+#[derive(Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub struct RuleTest {
+    pub sentence: String,
+    pub expected: Expectation,
+    #[serde(default)]
+    pub rule: Option<String>,
 }
 
 impl Default for Rules {
@@ -86,13 +266,145 @@ impl Default for Rules {
             replacements: vec![],
             even_symbols: vec![],
             matching_symbols: vec![],
+            dedup_unicode_normalization: false,
+            dedup_case_insensitive: false,
+            dedup_ignore_punctuation: false,
+            near_duplicate_threshold: 0.0,
+            near_duplicate_max_count: 1,
+            max_sentences_per_template: usize::MAX,
+            template_mask_capitalized: true,
+            segmenter_terminators: vec!['.', '?', '!'],
+            segmenter_abbreviations: vec![],
+            segmenter_closing_characters: CLOSING_CHARACTERS.to_vec(),
+            segmenter_ordinal_words: vec![],
+            segmenter_fallback: String::from(""),
+            srx_file: String::from(""),
+            word_tokenizer: String::from(""),
+            dictionary_words: HashSet::new(),
+            tests: vec![],
+        }
+    }
+}
+
+// The rules with all regexes and symbol lists prepared once, so that checking a
+// sentence does not need to parse the rules again. Derefs to the plain rules for
+// all other values.
+#[derive(Debug)]
+pub struct CompiledRules {
+    rules: Rules,
+    pub remove_brackets: Vec<Regex>,
+    pub allowed_symbols_regex: Option<Regex>,
+    pub disallowed_symbols: HashSet<char>,
+    pub stem_separator_regex: Option<Regex>,
+    pub abbreviation_patterns: Vec<Regex>,
+    pub other_patterns: Vec<Regex>,
+    pub dictionary_tokenizer: Option<DictionaryTokenizer>,
+}
+
+impl Deref for CompiledRules {
+    type Target = Rules;
+
+    fn deref(&self) -> &Rules {
+        &self.rules
+    }
+}
+
+impl FromStr for Rules {
+    type Err = String;
+
+    // Parses a single rules file. Inherited rules and disallowed words files need a
+    // language and a rules directory, use `load_rules_file` for those.
+    fn from_str(content: &str) -> Result<Rules, String> {
+        let table: Table = toml::from_str(content).map_err(|e| format!("{}", e))?;
+        if let Some(key) = INHERITANCE_KEYS.iter().find(|key| table.contains_key(**key)) {
+            return Err(format!("{} can't be resolved without a language, use load_rules_file instead", key));
+        }
+
+        toml::from_str(content).map_err(|e| format!("{}", e))
+    }
+}
+
+impl Rules {
+    pub fn from_path(path: &str) -> Result<Rules, String> {
+        let content = fs::read_to_string(path).map_err(|e| format!("{}: {}", path, e))?;
+        content.parse().map_err(|e| format!("{}: {}", path, e))
+    }
+
+    pub fn compile(&self) -> Result<CompiledRules, String> {
+        let remove_brackets = self.remove_brackets_list.iter()
+            .map(|(open, close)| {
+                compile_regex("remove_brackets_list", &format!(r#"\{}[^\{}\{}]*\{}"#, open, open, close, close))
+            })
+            .collect::<Result<Vec<_>, String>>()?;
+
+        Ok(CompiledRules {
+            rules: self.clone(),
+            remove_brackets,
+            allowed_symbols_regex: optional_regex("allowed_symbols_regex", &self.allowed_symbols_regex)?,
+            // Symbols with more than one character never matched a single character
+            disallowed_symbols: self.disallowed_symbols.iter()
+                .filter_map(|symbol| {
+                    let mut chars = symbol.chars();
+                    match (chars.next(), chars.next()) {
+                        (Some(symbol), None) => Some(symbol),
+                        _ => None,
+                    }
+                })
+                .collect(),
+            stem_separator_regex: optional_regex("stem_separator_regex", &self.stem_separator_regex)?,
+            abbreviation_patterns: regexes("abbreviation_patterns", &self.abbreviation_patterns)?,
+            other_patterns: regexes("other_patterns", &self.other_patterns)?,
+            dictionary_tokenizer: self.dictionary_tokenizer()?,
+        })
+    }
+
+    fn dictionary_tokenizer(&self) -> Result<Option<DictionaryTokenizer>, String> {
+        match self.word_tokenizer.as_str() {
+            "" => Ok(None),
+            "dictionary" => {
+                let tokenizer = DictionaryTokenizer::new(self.dictionary_words.iter());
+                if tokenizer.is_empty() {
+                    return Err(String::from("word_tokenizer: no dictionary_words found, add them to words/<language>.txt"));
+                }
+                Ok(Some(tokenizer))
+            },
+            other => Err(format!("word_tokenizer: {:?} is not supported", other)),
         }
     }
 }
 
+impl CompiledRules {
+    // The words the word based rules are checked against
+    pub fn words<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        match &self.dictionary_tokenizer {
+            Some(tokenizer) => tokenizer.tokenize(text),
+            None => text.split_whitespace().collect(),
+        }
+    }
+}
+
+fn compile_regex(name: &str, pattern: &str) -> Result<Regex, String> {
+    Regex::new(pattern).map_err(|e| format!("{}: {}", name, e))
+}
+
+fn optional_regex(name: &str, pattern: &str) -> Result<Option<Regex>, String> {
+    if pattern.is_empty() {
+        return Ok(None);
+    }
+
+    compile_regex(name, pattern).map(Some)
+}
+
+fn regexes(name: &str, patterns: &[String]) -> Result<Vec<Regex>, String> {
+    patterns.iter().map(|pattern| compile_regex(name, pattern)).collect()
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::rule_tests::run_rule_tests;
+    use crate::test_helpers::temp_dir_with;
+    use crate::validate::{validate_rules_str, Severity};
 
     #[test]
     fn test_default_rules() {
@@ -114,14 +426,202 @@ mod test {
         assert!(rules.needs_letter_start);
         assert_eq!(rules.remove_brackets_list, vec![]);
         assert_eq!(rules.allowed_symbols_regex, String::from(""));
-        assert_eq!(rules.disallowed_symbols, vec![]);
+        assert!(rules.disallowed_symbols.is_empty());
         assert_eq!(rules.disallowed_words, HashSet::new());
-        assert_eq!(rules.broken_whitespace, vec![]);
-        assert_eq!(rules.abbreviation_patterns, vec![]);
-        assert_eq!(rules.other_patterns, vec![]);
+        assert!(rules.broken_whitespace.is_empty());
+        assert!(rules.abbreviation_patterns.is_empty());
+        assert!(rules.other_patterns.is_empty());
         assert_eq!(rules.stem_separator_regex, String::from(""));
         assert_eq!(rules.replacements, vec![]);
-        assert_eq!(rules.even_symbols, vec![]);
+        assert!(rules.even_symbols.is_empty());
         assert_eq!(rules.matching_symbols, vec![]);
+        assert!(!rules.dedup_unicode_normalization);
+        assert!(!rules.dedup_case_insensitive);
+        assert!(!rules.dedup_ignore_punctuation);
+        assert_eq!(rules.near_duplicate_threshold, 0.0);
+        assert_eq!(rules.near_duplicate_max_count, 1);
+        assert_eq!(rules.max_sentences_per_template, usize::MAX);
+        assert!(rules.template_mask_capitalized);
+        assert_eq!(rules.segmenter_terminators, vec!['.', '?', '!']);
+        assert!(rules.segmenter_abbreviations.is_empty());
+        assert_eq!(rules.segmenter_closing_characters, CLOSING_CHARACTERS.to_vec());
+        assert!(rules.segmenter_ordinal_words.is_empty());
+        assert_eq!(rules.segmenter_fallback, "");
+        assert_eq!(rules.srx_file, "");
+        assert_eq!(rules.word_tokenizer, "");
+        assert_eq!(rules.dictionary_words, HashSet::new());
+        assert_eq!(rules.tests, vec![]);
+    }
+
+    #[test]
+    fn test_compile_rules() {
+        let rules : Rules = Rules {
+            disallowed_symbols: vec![String::from("%"), String::from("ab")],
+            other_patterns: vec![String::from("[0-9]")],
+            ..Default::default()
+        };
+
+        let compiled = rules.compile().unwrap();
+        assert_eq!(compiled.disallowed_symbols, HashSet::from(['%']));
+        assert_eq!(compiled.other_patterns.len(), 1);
+        assert!(compiled.allowed_symbols_regex.is_none());
+        assert_eq!(compiled.max_word_count, 14);
+    }
+
+    #[test]
+    fn test_compile_rules_invalid_regex() {
+        let rules : Rules = Rules {
+            other_patterns: vec![String::from("(unclosed")],
+            ..Default::default()
+        };
+
+        assert!(rules.compile().unwrap_err().starts_with("other_patterns: "));
+    }
+
+    #[test]
+    fn test_parse_rules() {
+        let rules : Rules = toml::from_str(r#"
+            replacements = [["Mr.", "Mister"]]
+            even_symbols = ['"']
+        "#).unwrap();
+
+        assert_eq!(rules.replacements, vec![(String::from("Mr."), String::from("Mister"))]);
+        assert_eq!(rules.even_symbols, vec![String::from("\"")]);
+    }
+
+    #[test]
+    fn test_rules_from_str() {
+        let rules: Rules = "max_word_count = 5\nreplacements = [[\"Mr.\", \"Mister\"]]".parse().unwrap();
+
+        assert_eq!(rules.max_word_count, 5);
+        assert_eq!(rules.replacements, vec![(String::from("Mr."), String::from("Mister"))]);
+        assert!("extends = \"en\"".parse::<Rules>().unwrap_err().starts_with("extends "));
+    }
+
+    #[test]
+    fn test_parse_rules_invalid_pair() {
+        let error = toml::from_str::<Rules>("replacements = [\n  [\"&\"],\n]").unwrap_err();
+
+        assert!(error.to_string().contains("line 2"), "{}", error);
+    }
+
+    #[test]
+    fn test_parse_rules_invalid_type() {
+        assert!(toml::from_str::<Rules>("other_patterns = [1]").is_err());
+    }
+
+    // Every rules file in this repository needs to load, must not have any errors and
+    // has to pass its own test sentences
+    #[test]
+    fn test_all_rules_files() {
+        for entry in std::fs::read_dir("./src/rules").unwrap() {
+            let path = entry.unwrap().path();
+            if path.extension().map(|extension| extension == "toml").unwrap_or(false) {
+                let language = path.file_stem().unwrap().to_string_lossy();
+                let rules = load_rules(&language, None);
+                assert!(rules.is_ok(), "{:?}", path);
+                assert_eq!(run_rule_tests(&rules.unwrap()), vec![], "{:?}", path);
+
+                let errors = validate_rules_str(&fs::read_to_string(&path).unwrap()).into_iter()
+                    .filter(|issue| issue.severity == Severity::Error)
+                    .collect::<Vec<_>>();
+                assert_eq!(errors, vec![], "{:?}", path);
+            }
+        }
+    }
+
+    #[test]
+    fn test_embedded_rules() {
+        let rules_file = read_language_rules_file(Some("/does/not/exist"), "en").unwrap();
+
+        assert_eq!(rules_file.name, "en.toml (embedded)");
+        assert_eq!(rules_file.content, fs::read_to_string("./src/rules/en.toml").unwrap());
+        assert!(read_rules_file(Some("/does/not/exist"), "disallowed_words/tr.txt").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_rules_dir() {
+        let rules_dir = temp_dir_with(&[("en.toml", "max_word_count = 5")]);
+
+        let rules = load_rules("en", rules_dir.path().to_str()).unwrap();
+        assert_eq!(rules.max_word_count, 5);
+    }
+
+    #[test]
+    fn test_unknown_language() {
+        assert!(load_rules("does-not-exist", None).is_err());
+    }
+
+    #[test]
+    fn test_extends() {
+        let rules_dir = temp_dir_with(&[
+            ("xx.toml", "max_word_count = 10\nmin_word_count = 2\nother_patterns = [\"foo\"]\nreplacements = [[\"a\", \"b\"]]\n"),
+            ("xx-YY.toml", "extends = \"xx\"\nmax_word_count = 12\nother_patterns = [\"bar\"]\n\n[append]\nreplacements = [[\"c\", \"d\"]]\n"),
+            ("disallowed_words/xx.txt", "Foo\n"),
+            ("disallowed_words/xx-YY.txt", "bar"),
+        ]);
+
+        let rules = resolve_rules("xx-YY", rules_dir.path().to_str()).unwrap();
+        assert_eq!(rules.max_word_count, 12);
+        assert_eq!(rules.min_word_count, 2);
+        assert_eq!(rules.other_patterns, vec![String::from("bar")]);
+        assert_eq!(rules.replacements, vec![
+            (String::from("a"), String::from("b")),
+            (String::from("c"), String::from("d")),
+        ]);
+        assert!(rules.disallowed_words.contains("foo"));
+        assert!(rules.disallowed_words.contains("bar"));
+    }
+
+    #[test]
+    fn test_extends_own_disallowed_words() {
+        let rules_dir = temp_dir_with(&[
+            ("xx.toml", "disallowed_words = [\"foo\"]\n"),
+            ("xx-YY.toml", "extends = \"xx\"\ndisallowed_words = [\"bar\"]\n"),
+            ("disallowed_words/xx.txt", "baz\n"),
+            ("disallowed_words/xx-YY.txt", "qux"),
+        ]);
+
+        let rules = resolve_rules("xx-YY", rules_dir.path().to_str()).unwrap();
+        assert!(!rules.disallowed_words.contains("foo"));
+        assert!(rules.disallowed_words.contains("bar"));
+        assert!(rules.disallowed_words.contains("baz"));
+        assert!(rules.disallowed_words.contains("qux"));
+    }
+
+    #[test]
+    fn test_extends_loop() {
+        let rules_dir = temp_dir_with(&[
+            ("xx.toml", "extends = \"yy\""),
+            ("yy.toml", "extends = \"xx\""),
+        ]);
+
+        assert_eq!(resolve_rules("xx", rules_dir.path().to_str()).unwrap_err(), "Rules extend each other in a loop: xx -> yy -> xx");
+    }
+
+    #[test]
+    fn test_append_to_value() {
+        let rules_dir = temp_dir_with(&[
+            ("xx.toml", "[append]\nmax_word_count = [1]"),
+        ]);
+
+        assert!(resolve_rules("xx", rules_dir.path().to_str()).is_err());
+    }
+
+    #[test]
+    fn test_dictionary_words() {
+        let rules_dir = temp_dir_with(&[
+            ("xx.toml", "word_tokenizer = \"dictionary\"\ndictionary_words = [\"北京\"]"),
+            ("words/xx.txt", "喜欢\n\n大学\n"),
+            ("yy.toml", "word_tokenizer = \"dictionary\""),
+            ("xx-YY.toml", "extends = \"xx\"\ndictionary_words = [\"我\"]"),
+        ]);
+
+        let rules = load_rules("xx", rules_dir.path().to_str()).unwrap();
+        assert_eq!(rules.dictionary_words.len(), 3);
+        assert_eq!(resolve_rules("xx-YY", rules_dir.path().to_str()).unwrap().dictionary_words.len(), 3);
+        assert_eq!(rules.words("喜欢北京大学"), vec!["喜欢", "北京", "大学"]);
+        assert!(load_rules("yy", rules_dir.path().to_str()).unwrap_err().contains("no dictionary_words found"));
+        assert_eq!(Rules::default().compile().unwrap().words(" a  b "), vec!["a", "b"]);
     }
 }
@@ -0,0 +1,258 @@
+use crate::checker::{check, Rejection};
+use crate::extractor::{load_file_names, split_sentences};
+use crate::loaders::Loader;
+use crate::rules::{load_rules_file, CompiledRules};
+use crate::segmenter::segmenter_for;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rayon::prelude::*;
+use std::collections::HashSet;
+use std::io::{self, Write};
+
+#[derive(Default)]
+pub struct DiffOptions {
+    pub old_rules_path: String,
+    pub new_rules_path: String,
+    pub rules_dir: Option<String>,
+    // Number of example sentences shown per category
+    pub samples: usize,
+    pub seed: Option<u64>,
+}
+
+#[derive(Debug, Default, PartialEq, Eq)]
+struct RulesDiff {
+    old_checked: usize,
+    new_checked: usize,
+    old_accepted: usize,
+    new_accepted: usize,
+    newly_accepted: Vec<String>,
+    newly_rejected: Vec<(String, Rejection)>,
+    // Accepted by both, but the replacements turned it into a different sentence
+    replaced: Vec<(String, String)>,
+}
+
+impl RulesDiff {
+    fn merge(&mut self, other: RulesDiff) {
+        self.old_checked += other.old_checked;
+        self.new_checked += other.new_checked;
+        self.old_accepted += other.old_accepted;
+        self.new_accepted += other.new_accepted;
+        self.newly_accepted.extend(other.newly_accepted);
+        self.newly_rejected.extend(other.newly_rejected);
+        self.replaced.extend(other.replaced);
+    }
+}
+
+// Compares the sentences both rule sets split a text into. The replacements are
+// applied before splitting, so they can change where a text gets split. If both
+// end up with the same number of sentences they are compared one by one.
+fn compare_text(
+    old_rules: &CompiledRules,
+    new_rules: &CompiledRules,
+    old_sentences: Vec<String>,
+    new_sentences: Vec<String>,
+) -> RulesDiff {
+    let old_results = old_sentences.iter().map(|sentence| check(old_rules, sentence)).collect::<Vec<_>>();
+    let new_results = new_sentences.iter().map(|sentence| check(new_rules, sentence)).collect::<Vec<_>>();
+    let mut diff = RulesDiff {
+        old_checked: old_sentences.len(),
+        new_checked: new_sentences.len(),
+        old_accepted: old_results.iter().filter(|result| result.is_ok()).count(),
+        new_accepted: new_results.iter().filter(|result| result.is_ok()).count(),
+        ..Default::default()
+    };
+
+    if old_sentences.len() == new_sentences.len() {
+        let pairs = old_sentences.iter().zip(old_results).zip(new_sentences.iter().zip(new_results));
+        for ((old_sentence, old_result), (new_sentence, new_result)) in pairs {
+            match (old_result, new_result) {
+                (Err(_), Ok(())) => diff.newly_accepted.push(new_sentence.trim().to_string()),
+                (Ok(()), Err(rejection)) => diff.newly_rejected.push((new_sentence.trim().to_string(), rejection)),
+                (Ok(()), Ok(())) if old_sentence != new_sentence => {
+                    diff.replaced.push((old_sentence.trim().to_string(), new_sentence.trim().to_string()));
+                },
+                _ => {},
+            }
+        }
+        return diff;
+    }
+
+    // The text was split differently, so every sentence of the new rules is looked up
+    // in all sentences of the old rules instead
+    let old_accepted = old_sentences.iter().zip(old_results.iter())
+        .filter(|(_, result)| result.is_ok())
+        .map(|(sentence, _)| sentence)
+        .collect::<HashSet<_>>();
+    let old_rejected = old_sentences.iter().zip(old_results.iter())
+        .filter(|(_, result)| result.is_err())
+        .map(|(sentence, _)| sentence)
+        .collect::<HashSet<_>>();
+    for (sentence, result) in new_sentences.iter().zip(new_results) {
+        match result {
+            Ok(()) if !old_accepted.contains(sentence) => diff.newly_accepted.push(sentence.trim().to_string()),
+            Err(rejection) if !old_rejected.contains(sentence) => diff.newly_rejected.push((sentence.trim().to_string(), rejection)),
+            _ => {},
+        }
+    }
+
+    diff
+}
+
+fn percentage(count: usize, total: usize) -> f64 {
+    if total == 0 {
+        return 0.0;
+    }
+
+    count as f64 * 100.0 / total as f64
+}
+
+fn write_report(writer: &mut impl Write, diff: &RulesDiff, samples: usize, rng: &mut impl Rng) -> io::Result<()> {
+    writeln!(
+        writer, "Old rules accepted {} of {} sentences ({:.2}%)",
+        diff.old_accepted, diff.old_checked, percentage(diff.old_accepted, diff.old_checked),
+    )?;
+    writeln!(
+        writer, "New rules accepted {} of {} sentences ({:.2}%)",
+        diff.new_accepted, diff.new_checked, percentage(diff.new_accepted, diff.new_checked),
+    )?;
+
+    writeln!(writer, "\nNewly accepted: {}", diff.newly_accepted.len())?;
+    for sentence in diff.newly_accepted.choose_multiple(rng, samples) {
+        writeln!(writer, "  + {}", sentence)?;
+    }
+
+    writeln!(writer, "\nNewly rejected: {}", diff.newly_rejected.len())?;
+    for (sentence, rejection) in diff.newly_rejected.choose_multiple(rng, samples) {
+        writeln!(writer, "  - {} [{}]", sentence, rejection)?;
+    }
+
+    writeln!(writer, "\nChanged by replacements: {}", diff.replaced.len())?;
+    for (old, new) in diff.replaced.choose_multiple(rng, samples) {
+        writeln!(writer, "  - {}", old)?;
+        writeln!(writer, "  + {}", new)?;
+    }
+
+    Ok(())
+}
+
+pub fn diff_rules(loader: impl Loader, options: DiffOptions) -> Result<(), String> {
+    let config = loader.get_config();
+    let rules_dir = options.rules_dir.as_deref();
+    let old_rules = load_rules_file(&options.old_rules_path, &config.language, rules_dir)?;
+    let new_rules = load_rules_file(&options.new_rules_path, &config.language, rules_dir)?;
+    let old_segmenter = segmenter_for(&old_rules, &config.language, rules_dir)?;
+    let new_segmenter = segmenter_for(&new_rules, &config.language, rules_dir)?;
+    let seed = options.seed.unwrap_or_else(|| rand::thread_rng().gen());
+    let mut rng = StdRng::seed_from_u64(seed);
+
+    let mut diff = RulesDiff::default();
+    for file_name in load_file_names(&config.directory, &config.file_prefix)? {
+        eprintln!("file_name = {:?}", file_name.to_string_lossy());
+        loader.load_chunks(&file_name, &HashSet::new(), &mut |texts| {
+            let text_diffs = texts.par_iter().map(|text| {
+                let old_sentences = split_sentences(&old_rules, &text.content, old_segmenter.as_ref());
+                let new_sentences = split_sentences(&new_rules, &text.content, new_segmenter.as_ref());
+                compare_text(&old_rules, &new_rules, old_sentences, new_sentences)
+            }).collect::<Vec<_>>();
+            for text_diff in text_diffs {
+                diff.merge(text_diff);
+            }
+            Ok(())
+        })?;
+    }
+
+    eprintln!("seed = {:?}", seed);
+    write_report(&mut io::stdout().lock(), &diff, options.samples, &mut rng).map_err(|e| format!("{}", e))
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::rules::Rules;
+
+    fn rules(max_word_count: usize, replacements: Vec<(&str, &str)>) -> CompiledRules {
+        Rules {
+            max_word_count,
+            replacements: replacements.into_iter()
+                .map(|(search, replacement)| (String::from(search), String::from(replacement)))
+                .collect(),
+            ..Default::default()
+        }.compile().unwrap()
+    }
+
+    fn sentences(sentences: &[&str]) -> Vec<String> {
+        sentences.iter().map(|sentence| String::from(*sentence)).collect()
+    }
+
+    #[test]
+    fn test_compare_text() {
+        let old_rules = rules(4, vec![]);
+        let new_rules = rules(3, vec![]);
+        let old_sentences = sentences(&["This is fine", "This is not fine", "This is much too long"]);
+        let new_sentences = old_sentences.clone();
+
+        assert_eq!(compare_text(&old_rules, &new_rules, old_sentences, new_sentences), RulesDiff {
+            old_checked: 3,
+            new_checked: 3,
+            old_accepted: 2,
+            new_accepted: 1,
+            newly_rejected: vec![(String::from("This is not fine"), Rejection::MaxWordCount)],
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_compare_text_replaced() {
+        let old_rules = rules(4, vec![]);
+        let new_rules = rules(4, vec![("Mr.", "Mister")]);
+        let old_sentences = sentences(&["Hello Mr. Smith"]);
+        let new_sentences = sentences(&["Hello Mister Smith"]);
+
+        assert_eq!(compare_text(&old_rules, &new_rules, old_sentences, new_sentences).replaced, vec![
+            (String::from("Hello Mr. Smith"), String::from("Hello Mister Smith")),
+        ]);
+    }
+
+    #[test]
+    fn test_compare_text_split_differently() {
+        let old_rules = rules(4, vec![]);
+        let new_rules = rules(4, vec![("Mr.", "Mister")]);
+        let old_sentences = sentences(&["Hello Mr.", "Smith is here"]);
+        let new_sentences = sentences(&["Hello Mister Smith is here"]);
+
+        assert_eq!(compare_text(&old_rules, &new_rules, old_sentences, new_sentences), RulesDiff {
+            old_checked: 2,
+            new_checked: 1,
+            old_accepted: 2,
+            new_accepted: 0,
+            newly_rejected: vec![(String::from("Hello Mister Smith is here"), Rejection::MaxWordCount)],
+            ..Default::default()
+        });
+    }
+
+    #[test]
+    fn test_write_report() {
+        let diff = RulesDiff {
+            old_checked: 3,
+            new_checked: 3,
+            old_accepted: 2,
+            new_accepted: 1,
+            newly_rejected: vec![(String::from("This is not fine"), Rejection::MaxWordCount)],
+            ..Default::default()
+        };
+
+        let mut output = vec![];
+        write_report(&mut output, &diff, 10, &mut StdRng::seed_from_u64(42)).unwrap();
+        assert_eq!(String::from_utf8(output).unwrap(), "Old rules accepted 2 of 3 sentences (66.67%)
+New rules accepted 1 of 3 sentences (33.33%)
+
+Newly accepted: 0
+
+Newly rejected: 1
+  - This is not fine [max_word_count]
+
+Changed by replacements: 0
+");
+    }
+}
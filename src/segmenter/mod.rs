@@ -0,0 +1,105 @@
+use crate::rules::Rules;
+
+pub use rule_based::{RuleBasedSegmenter, CLOSING_CHARACTERS};
+pub use spaceless::SpacelessSegmenter;
+pub use rust_punkt::{model_file_name, PunktModel, PunktSegmenter};
+pub use srx::SrxSegmenter;
+
+#[cfg(feature = "python")]
+mod python;
+mod rule_based;
+mod rust_punkt;
+mod spaceless;
+mod srx;
+
+// All values of the `segmenter` rule, an empty value uses rust-punkt
+pub const SEGMENTERS: [&str; 9] = ["", "punkt", "python", "rules", "spaceless", "srx", "bengali", "german", "turkish"];
+
+// Splits a text into sentences. The replacements of the rules have already been
+// applied to the text at this point.
+pub trait Segmenter: Send + Sync {
+    fn split(&self, text: &str) -> Vec<String>;
+}
+
+// Trained models and other files needed by the segmenter are read from the rules directory
+pub fn segmenter_for(rules: &Rules, language: &str, rules_dir: Option<&str>) -> Result<Box<dyn Segmenter>, String> {
+    match rules.segmenter.as_str() {
+        "" | "punkt" => Ok(Box::new(PunktSegmenter::new(language, rules_dir)?)),
+        "rules" => Ok(Box::new(RuleBasedSegmenter::from_rules(rules))),
+        "spaceless" => Ok(Box::new(SpacelessSegmenter::from_rules(rules))),
+        "srx" => Ok(Box::new(SrxSegmenter::new(rules, language, rules_dir)?)),
+        "bengali" => Ok(Box::new(RuleBasedSegmenter::bengali())),
+        "german" => Ok(Box::new(RuleBasedSegmenter::german())),
+        "turkish" => Ok(Box::new(RuleBasedSegmenter::turkish())),
+        "python" => python_segmenter(rules, language, rules_dir),
+        other => Err(format!("Segmenter {:?} is not supported", other)),
+    }
+}
+
+#[cfg(feature = "python")]
+fn python_segmenter(_rules: &Rules, language: &str, _rules_dir: Option<&str>) -> Result<Box<dyn Segmenter>, String> {
+    Ok(Box::new(python::PythonSegmenter { language: language.to_string() }))
+}
+
+// Without the python feature the segmenter_fallback is used, which splits some texts
+// differently than the Python segmenter does. It needs to be set explicitly, otherwise
+// the same rules would silently extract other sentences depending on the build.
+#[cfg(not(feature = "python"))]
+fn python_segmenter(rules: &Rules, language: &str, rules_dir: Option<&str>) -> Result<Box<dyn Segmenter>, String> {
+    if rules.segmenter_fallback.is_empty() || rules.segmenter_fallback == "python" {
+        return Err(String::from(
+            "The python segmenter is only available when built with `--features python`, set segmenter_fallback to use another segmenter without it"
+        ));
+    }
+    eprintln!("Built without `--features python`, using segmenter_fallback = {:?}", rules.segmenter_fallback);
+
+    let fallback = Rules {
+        segmenter: rules.segmenter_fallback.clone(),
+        ..rules.clone()
+    };
+    segmenter_for(&fallback, language, rules_dir)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn segmenter(name: &str) -> Result<Box<dyn Segmenter>, String> {
+        let rules = Rules {
+            segmenter: String::from(name),
+            ..Default::default()
+        };
+
+        segmenter_for(&rules, "en", None)
+    }
+
+    #[test]
+    fn test_segmenter_for() {
+        for name in SEGMENTERS.iter().filter(|name| !["python", "srx"].contains(name)) {
+            assert!(segmenter(name).is_ok(), "{:?}", name);
+        }
+        assert_eq!(segmenter("german").unwrap().split("Das ist ein Satz. Ich auch!").len(), 2);
+    }
+
+    #[test]
+    fn test_segmenter_for_unknown() {
+        assert_eq!(segmenter("unknown").err().unwrap(), "Segmenter \"unknown\" is not supported");
+    }
+
+    #[cfg(not(feature = "python"))]
+    #[test]
+    fn test_segmenter_for_python_without_feature() {
+        let rules = Rules {
+            segmenter: String::from("python"),
+            ..Default::default()
+        };
+        assert!(segmenter_for(&rules, "de", None).is_err());
+
+        let rules = Rules {
+            segmenter_fallback: String::from("german"),
+            ..rules
+        };
+        let segmenter = segmenter_for(&rules, "de", None).unwrap();
+        assert_eq!(segmenter.split("Das ist ein Satz. Ich auch!").len(), 2);
+    }
+}
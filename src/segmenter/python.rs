@@ -1,5 +1,17 @@
+use super::Segmenter;
 use inline_python::{python, Context};
 
+// Runs the Python segmenter of the language, needs the `python` feature
+pub struct PythonSegmenter {
+    pub language: String,
+}
+
+impl Segmenter for PythonSegmenter {
+    fn split(&self, text: &str) -> Vec<String> {
+        split_sentences_with_python(&self.language, text)
+    }
+}
+
 pub fn split_sentences_with_python(language: &str, text: &str) -> Vec<String> {
     match language {
         "en" => split_sentences_with_python_en(text),
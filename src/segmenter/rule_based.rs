@@ -0,0 +1,255 @@
+use super::Segmenter;
+use crate::rules::Rules;
+use std::collections::HashSet;
+
+// Characters following the end of a sentence which still belong to it
+pub const CLOSING_CHARACTERS: [char; 10] = ['"', '\'', '“', '”', '‘', '’', '«', '»', ')', ']'];
+
+const GERMAN_ABBREVIATIONS: [&str; 58] = [
+    "abs.", "allg.", "art.", "bd.", "bspw.", "bzw.", "ca.", "chr.", "d.h.", "dr.", "dt.", "ehem.",
+    "engl.", "etc.", "evtl.", "fr.", "frz.", "geb.", "gegr.", "gest.", "ggf.", "griech.", "hl.",
+    "hr.", "hrsg.", "inkl.", "ital.", "jh.", "jhd.", "lat.", "lt.", "max.", "min.", "mio.", "mrd.",
+    "nr.", "o.ä.", "prof.", "s.", "sog.", "span.", "st.", "std.", "str.", "tel.", "u.a.", "u.ä.",
+    "u.u.", "u.v.m.", "usw.", "v.a.", "v.chr.", "n.chr.", "vgl.", "z.b.", "z.t.", "z.zt.", "zit.",
+];
+
+const GERMAN_MONTHS: [&str; 13] = [
+    "januar", "jänner", "februar", "märz", "april", "mai", "juni", "juli", "august", "september",
+    "oktober", "november", "dezember",
+];
+
+const TURKISH_ABBREVIATIONS: [&str; 37] = [
+    "a.ş.", "age.", "alb.", "apt.", "av.", "bkz.", "bl.", "cad.", "doç.", "doğ.", "dr.", "gen.",
+    "hz.", "kur.", "ltd.", "m.ö.", "m.s.", "mah.", "müh.", "no.", "org.", "örn.", "öğr.", "prof.",
+    "s.", "sn.", "sok.", "st.", "şti.", "t.c.", "tel.", "vb.", "vs.", "yrd.", "yy.", "yük.", "ünv.",
+];
+
+const TURKISH_MONTHS: [&str; 12] = [
+    "ocak", "şubat", "mart", "nisan", "mayıs", "haziran", "temmuz", "ağustos", "eylül", "ekim",
+    "kasım", "aralık",
+];
+
+// Splits after the given sentence terminators, if they are followed by whitespace and
+// the next sentence does not start with a lowercase letter. Known abbreviations don't
+// end a sentence, and neither do single letters such as initials. A number followed by
+// a dot only doesn't end a sentence if the next word is one of the ordinal words, such
+// as "3. Oktober", otherwise it's usually a year or another number ending the sentence.
+pub struct RuleBasedSegmenter {
+    pub terminators: HashSet<char>,
+    // Lowercase abbreviations including their dots, such as "z.b."
+    pub abbreviations: HashSet<String>,
+    pub closing_characters: HashSet<char>,
+    // Lowercase words which follow ordinal numbers, such as month names
+    pub ordinal_words: HashSet<String>,
+}
+
+impl RuleBasedSegmenter {
+    pub fn new(terminators: &[char], abbreviations: &[&str]) -> RuleBasedSegmenter {
+        RuleBasedSegmenter {
+            terminators: terminators.iter().copied().collect(),
+            abbreviations: abbreviations.iter().map(|abbreviation| abbreviation.to_lowercase()).collect(),
+            closing_characters: CLOSING_CHARACTERS.iter().copied().collect(),
+            ordinal_words: HashSet::new(),
+        }
+    }
+
+    pub fn with_ordinal_words(mut self, ordinal_words: &[&str]) -> RuleBasedSegmenter {
+        self.ordinal_words = ordinal_words.iter().map(|word| word.to_lowercase()).collect();
+        self
+    }
+
+    // Configured by the `segmenter_*` values of the rules file
+    pub fn from_rules(rules: &Rules) -> RuleBasedSegmenter {
+        RuleBasedSegmenter {
+            terminators: rules.segmenter_terminators.iter().copied().collect(),
+            abbreviations: rules.segmenter_abbreviations.iter().map(|abbreviation| abbreviation.to_lowercase()).collect(),
+            closing_characters: rules.segmenter_closing_characters.iter().copied().collect(),
+            ordinal_words: rules.segmenter_ordinal_words.iter().map(|word| word.to_lowercase()).collect(),
+        }
+    }
+
+    // Bengali ends sentences with a danda instead of a dot
+    pub fn bengali() -> RuleBasedSegmenter {
+        RuleBasedSegmenter::new(&['।', '॥', '?', '!'], &[])
+    }
+
+    pub fn german() -> RuleBasedSegmenter {
+        RuleBasedSegmenter::new(&['.', '?', '!'], &GERMAN_ABBREVIATIONS).with_ordinal_words(&GERMAN_MONTHS)
+    }
+
+    pub fn turkish() -> RuleBasedSegmenter {
+        RuleBasedSegmenter::new(&['.', '?', '!'], &TURKISH_ABBREVIATIONS).with_ordinal_words(&TURKISH_MONTHS)
+    }
+
+    // The word includes the terminator it ends with
+    fn is_abbreviation(&self, word: &str, terminator: char) -> bool {
+        let word = word.trim_start_matches(|c: char| !c.is_alphanumeric());
+        if self.abbreviations.contains(&word.to_lowercase()) {
+            return true;
+        }
+        if terminator != '.' {
+            return false;
+        }
+
+        let mut chars = word.trim_end_matches('.').chars();
+        matches!((chars.next(), chars.next()), (Some(c), None) if c.is_alphabetic())
+    }
+
+    // The word includes the terminator it ends with
+    fn is_ordinal(&self, word: &str, terminator: char, next_word: &str) -> bool {
+        let number = word.trim_start_matches(|c: char| !c.is_alphanumeric()).trim_end_matches('.');
+        let next_word = next_word.trim_matches(|c: char| !c.is_alphanumeric()).to_lowercase();
+
+        terminator == '.'
+            && !number.is_empty()
+            && number.chars().all(|c| c.is_ascii_digit())
+            && self.ordinal_words.contains(&next_word)
+    }
+}
+
+impl Segmenter for RuleBasedSegmenter {
+    fn split(&self, text: &str) -> Vec<String> {
+        let mut sentences = vec![];
+        let mut start = 0;
+        let mut chars = text.char_indices().peekable();
+        while let Some((index, c)) = chars.next() {
+            if !self.terminators.contains(&c) {
+                continue;
+            }
+
+            // Repeated terminators and closing quotes or brackets belong to the sentence
+            let mut end = index + c.len_utf8();
+            while let Some(&(next_index, next)) = chars.peek() {
+                if !self.terminators.contains(&next) && !self.closing_characters.contains(&next) {
+                    break;
+                }
+                end = next_index + next.len_utf8();
+                chars.next();
+            }
+
+            let rest = &text[end..];
+            if !rest.starts_with(char::is_whitespace) {
+                continue;
+            }
+            let next_sentence_lowercase = rest.trim_start().chars().next()
+                .map(|next| next.is_lowercase())
+                .unwrap_or(true);
+            if next_sentence_lowercase {
+                continue;
+            }
+            let word = text[start..index + c.len_utf8()].split_whitespace().last().unwrap_or_default();
+            let next_word = rest.split_whitespace().next().unwrap_or_default();
+            if self.is_abbreviation(word, c) || self.is_ordinal(word, c, next_word) {
+                continue;
+            }
+
+            let sentence = text[start..end].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            start = end;
+        }
+
+        let sentence = text[start..].trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+
+        sentences
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_bengali() {
+        let segmenter = RuleBasedSegmenter::bengali();
+
+        assert_eq!(
+            segmenter.split("আমি প্রথম বাক্য। আমি আর একটি বাক্য। তুমি কে?"),
+            vec!["আমি প্রথম বাক্য।", "আমি আর একটি বাক্য।", "তুমি কে?"]
+        );
+        assert_eq!(segmenter.split("ড. রহমান এসেছেন।"), vec!["ড. রহমান এসেছেন।"]);
+    }
+
+    #[test]
+    fn test_german() {
+        let segmenter = RuleBasedSegmenter::german();
+
+        assert_eq!(segmenter.split("I am a sentence. Me too!"), vec!["I am a sentence.", "Me too!"]);
+        assert_eq!(
+            segmenter.split("Das ist z. B. ein Satz. Das ist z.B. ein anderer Satz, vgl. Dr. Müller."),
+            vec!["Das ist z. B. ein Satz.", "Das ist z.B. ein anderer Satz, vgl. Dr. Müller."]
+        );
+        assert_eq!(
+            segmenter.split("Am 3. Oktober kam J. S. Bach. Er blieb bis 300 n. Chr. in der Stadt."),
+            vec!["Am 3. Oktober kam J. S. Bach.", "Er blieb bis 300 n. Chr. in der Stadt."]
+        );
+    }
+
+    #[test]
+    fn test_german_year() {
+        let segmenter = RuleBasedSegmenter::german();
+
+        assert_eq!(
+            segmenter.split("Das geschah im Jahr 1990. Dann kam die Wende. Am 9. November fiel die Mauer."),
+            vec!["Das geschah im Jahr 1990.", "Dann kam die Wende.", "Am 9. November fiel die Mauer."]
+        );
+    }
+
+    #[test]
+    fn test_german_quotes() {
+        let segmenter = RuleBasedSegmenter::german();
+
+        assert_eq!(
+            segmenter.split("Er sagte: „Ich komme.“ Dann ging er. „Wohin?“, fragte sie. Das war's..."),
+            vec!["Er sagte: „Ich komme.“", "Dann ging er.", "„Wohin?“, fragte sie.", "Das war's..."]
+        );
+    }
+
+    #[test]
+    fn test_turkish() {
+        let segmenter = RuleBasedSegmenter::turkish();
+
+        assert_eq!(segmenter.split("Ben bir cümleyim. Ben de!"), vec!["Ben bir cümleyim.", "Ben de!"]);
+        assert_eq!(
+            segmenter.split("Prof. Dr. Ahmet geldi. 19. yüzyılda İstanbul büyüdü. Doç. Ayşe de geldi."),
+            vec!["Prof. Dr. Ahmet geldi.", "19. yüzyılda İstanbul büyüdü.", "Doç. Ayşe de geldi."]
+        );
+        assert_eq!(
+            segmenter.split("Okul 1990. Sonra kapandı. 1. Ocak günü açıldı."),
+            vec!["Okul 1990.", "Sonra kapandı.", "1. Ocak günü açıldı."]
+        );
+    }
+
+    #[test]
+    fn test_from_rules() {
+        let rules: Rules = toml::from_str(r#"
+            segmenter_terminators = ["।", "?", "."]
+            segmenter_abbreviations = ["डॉ."]
+            segmenter_closing_characters = ["\""]
+            segmenter_ordinal_words = ["जनवरी"]
+        "#).unwrap();
+        let segmenter = RuleBasedSegmenter::from_rules(&rules);
+
+        assert_eq!(
+            segmenter.split("यह पहला वाक्य है। \"क्या यह दूसरा है?\" हाँ। डॉ. शर्मा आए। नहीं."),
+            vec!["यह पहला वाक्य है।", "\"क्या यह दूसरा है?\"", "हाँ।", "डॉ. शर्मा आए।", "नहीं."]
+        );
+        assert_eq!(
+            segmenter.split("वह 26. जनवरी को आया। साल 1990. अब नहीं."),
+            vec!["वह 26. जनवरी को आया।", "साल 1990.", "अब नहीं."]
+        );
+    }
+
+    #[test]
+    fn test_lowercase_continuation() {
+        let segmenter = RuleBasedSegmenter::new(&['.', '?', '!'], &[]);
+
+        assert_eq!(segmenter.split("Im Jahr 1990. war es so. Im Jahr 1990. Neu!"), vec!["Im Jahr 1990. war es so.", "Im Jahr 1990.", "Neu!"]);
+        assert_eq!(segmenter.split("Das ist unbek. abgekürzt. Neu!"), vec!["Das ist unbek. abgekürzt.", "Neu!"]);
+        assert_eq!(segmenter.split(""), Vec::<String>::new());
+    }
+}
@@ -0,0 +1,186 @@
+use super::Segmenter;
+use crate::rules::read_rules_file;
+use punkt::params::Standard;
+use punkt::{SentenceTokenizer, Trainer, TrainingData};
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, BTreeSet};
+
+// The type punkt uses for all tokens that are numbers
+const NUMBER_TYPE: &str = "##number##";
+
+// The default segmenter, using the rust-punkt training data of the language
+pub struct PunktSegmenter {
+    pub training_data: TrainingData,
+}
+
+impl PunktSegmenter {
+    // Uses the model trained with `train-segmenter` if there is one in the rules
+    // directory, otherwise the training data shipped with rust-punkt
+    pub fn new(language: &str, rules_dir: Option<&str>) -> Result<PunktSegmenter, String> {
+        let model_file = read_rules_file(rules_dir, &model_file_name(language))?;
+        eprintln!("Using punkt model = {:?}", model_file.as_ref().map(|file| &file.name));
+        let training_data = match model_file {
+            Some(model_file) => {
+                let model: PunktModel = serde_json::from_str(&model_file.content)
+                    .map_err(|e| format!("{}: {}", model_file.name, e))?;
+                model.training_data()
+            },
+            None => get_training_data(language),
+        };
+
+        Ok(PunktSegmenter { training_data })
+    }
+}
+
+impl Segmenter for PunktSegmenter {
+    fn split(&self, text: &str) -> Vec<String> {
+        SentenceTokenizer::<Standard>::new(text, &self.training_data)
+            .map(|item| { String::from(item) })
+            .collect()
+    }
+}
+
+pub fn get_training_data(language: &str) -> TrainingData {
+    match language {
+        "cs" => TrainingData::czech(),
+        "de" => TrainingData::german(),
+        "dk" => TrainingData::danish(),
+        "el" => TrainingData::greek(),
+        "en" => TrainingData::english(),
+        "es" => TrainingData::spanish(),
+        "et" => TrainingData::estonian(),
+        "fi" => TrainingData::finnish(),
+        "fr" => TrainingData::french(),
+        "it" => TrainingData::italian(),
+        "nl" => TrainingData::dutch(),
+        "no" => TrainingData::norwegian(),
+        "pl" => TrainingData::polish(),
+        "pt" => TrainingData::portuguese(),
+        "se" => TrainingData::swedish(),
+        "sl" => TrainingData::slovene(),
+        "tr" => TrainingData::turkish(),
+        _ => TrainingData::english(),
+    }
+}
+
+// Path of the trained model within the rules directory
+pub fn model_file_name(language: &str) -> String {
+    format!("punkt/{}.json", language)
+}
+
+// The parameters punkt learned from a corpus, in a form that can be saved
+#[derive(Debug, Default, PartialEq, Eq, Deserialize, Serialize)]
+#[serde(default)]
+pub struct PunktModel {
+    pub abbreviations: BTreeSet<String>,
+    pub collocations: BTreeSet<(String, String)>,
+    pub sentence_starters: BTreeSet<String>,
+    pub orthographic_context: BTreeMap<String, u8>,
+}
+
+impl PunktModel {
+    // Trains punkt on the text. The training data of punkt can only be queried for
+    // single tokens, so every token of the text is looked up to find the learned ones.
+    pub fn train(text: &str) -> PunktModel {
+        let mut training_data = TrainingData::new();
+        Trainer::<Standard>::new().train(text, &mut training_data);
+
+        let mut types = text.split_whitespace().map(token_type).collect::<Vec<_>>();
+        types.retain(|token_type| !token_type.is_empty());
+        let mut model = PunktModel::default();
+        for pair in types.windows(2) {
+            if training_data.contains_collocation(&pair[0], &pair[1]) {
+                model.collocations.insert((pair[0].clone(), pair[1].clone()));
+            }
+        }
+
+        for token_type in types.into_iter().collect::<BTreeSet<_>>() {
+            if training_data.contains_abbrev(&token_type) {
+                model.abbreviations.insert(token_type.clone());
+            }
+            if training_data.contains_sentence_starter(&token_type) {
+                model.sentence_starters.insert(token_type.clone());
+            }
+            let context = training_data.get_orthographic_context(&token_type);
+            if context != 0 {
+                model.orthographic_context.insert(token_type, context);
+            }
+        }
+
+        model
+    }
+
+    pub fn training_data(&self) -> TrainingData {
+        let mut training_data = TrainingData::new();
+        for abbreviation in self.abbreviations.iter() {
+            training_data.insert_abbrev(abbreviation);
+        }
+        for (left, right) in self.collocations.iter() {
+            training_data.insert_collocation(left, right);
+        }
+        for sentence_starter in self.sentence_starters.iter() {
+            training_data.insert_sentence_starter(sentence_starter);
+        }
+        for (token_type, context) in self.orthographic_context.iter() {
+            training_data.insert_orthographic_context(token_type, *context);
+        }
+
+        training_data
+    }
+}
+
+// Punkt compares tokens lowercased, without surrounding punctuation and the final
+// dot, and treats all numbers the same
+fn token_type(token: &str) -> String {
+    let token = token
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim_end_matches(|c: char| !c.is_alphanumeric() && c != '.')
+        .trim_end_matches('.');
+    if !token.is_empty() && token.chars().all(|c| c.is_numeric() || c == '.' || c == ',') {
+        return String::from(NUMBER_TYPE);
+    }
+
+    token.to_lowercase()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::temp_dir_with;
+
+    #[test]
+    fn test_token_type() {
+        assert_eq!(token_type("„Abk.“"), "abk");
+        assert_eq!(token_type("z.B."), "z.b");
+        assert_eq!(token_type("1.234,5"), NUMBER_TYPE);
+        assert_eq!(token_type("--"), "");
+    }
+
+    #[test]
+    fn test_model_roundtrip() {
+        let model = PunktModel {
+            abbreviations: BTreeSet::from([String::from("abk")]),
+            collocations: BTreeSet::from([(String::from(NUMBER_TYPE), String::from("oktober"))]),
+            sentence_starters: BTreeSet::from([String::from("dann")]),
+            orthographic_context: BTreeMap::from([(String::from("dann"), 6)]),
+        };
+
+        let training_data = model.training_data();
+        assert!(training_data.contains_abbrev("abk"));
+        assert!(training_data.contains_collocation(NUMBER_TYPE, "oktober"));
+        assert!(training_data.contains_sentence_starter("dann"));
+        assert_eq!(training_data.get_orthographic_context("dann"), 6);
+
+        let json = serde_json::to_string(&model).unwrap();
+        assert_eq!(serde_json::from_str::<PunktModel>(&json).unwrap(), model);
+    }
+
+    #[test]
+    fn test_load_model() {
+        let rules_dir = temp_dir_with(&[("punkt/xx.json", r#"{"abbreviations": ["abk"]}"#)]);
+
+        let segmenter = PunktSegmenter::new("xx", rules_dir.path().to_str()).unwrap();
+        assert!(segmenter.training_data.contains_abbrev("abk"));
+        assert!(PunktSegmenter::new("yy", rules_dir.path().to_str()).is_ok());
+    }
+}
@@ -0,0 +1,130 @@
+use super::Segmenter;
+use crate::rules::Rules;
+use crate::tokenizer::{DictionaryTokenizer, LowercaseText};
+use std::collections::HashSet;
+
+// Sentence terminators of Chinese and Japanese, which are always used
+pub const FULL_WIDTH_TERMINATORS: [char; 4] = ['。', '！', '？', '｡'];
+
+const FULL_WIDTH_CLOSING_CHARACTERS: [char; 8] = ['」', '』', '）', '】', '》', '〉', '〕', '］'];
+
+// Splits texts of scripts without spaces between words, such as Chinese, Japanese or
+// Khmer. Unlike the "rules" segmenter no whitespace is needed after a terminator, as
+// sentences follow each other without it. A terminator does not end a sentence if
+// it's part of one of the dictionary_words, or followed by a letter or digit of the
+// Latin script, as in numbers or domain names.
+pub struct SpacelessSegmenter {
+    pub terminators: HashSet<char>,
+    pub closing_characters: HashSet<char>,
+    // Only the dictionary words containing a terminator are needed
+    pub words: DictionaryTokenizer,
+}
+
+impl SpacelessSegmenter {
+    pub fn from_rules(rules: &Rules) -> SpacelessSegmenter {
+        let terminators = FULL_WIDTH_TERMINATORS.iter()
+            .chain(rules.segmenter_terminators.iter())
+            .copied()
+            .collect::<HashSet<_>>();
+        let words = rules.dictionary_words.iter()
+            .filter(|word| word.chars().count() > 1 && word.contains(|c: char| terminators.contains(&c)));
+
+        SpacelessSegmenter {
+            words: DictionaryTokenizer::new(words),
+            closing_characters: FULL_WIDTH_CLOSING_CHARACTERS.iter()
+                .chain(rules.segmenter_closing_characters.iter())
+                .copied()
+                .collect(),
+            terminators,
+        }
+    }
+}
+
+impl Segmenter for SpacelessSegmenter {
+    fn split(&self, text: &str) -> Vec<String> {
+        let lowercase_text = LowercaseText::new(text);
+        let mut sentences = vec![];
+        let mut start = 0;
+        let mut index = 0;
+        while let Some(c) = text[index..].chars().next() {
+            if let Some(length) = self.words.longest_match(&lowercase_text, index) {
+                index += length;
+                continue;
+            }
+            index += c.len_utf8();
+            if !self.terminators.contains(&c) {
+                continue;
+            }
+
+            // Repeated terminators and closing quotes or brackets belong to the sentence
+            while let Some(next) = text[index..].chars().next() {
+                if !self.terminators.contains(&next) && !self.closing_characters.contains(&next) {
+                    break;
+                }
+                index += next.len_utf8();
+            }
+            if text[index..].starts_with(|next: char| next.is_ascii_alphanumeric()) {
+                continue;
+            }
+
+            let sentence = text[start..index].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            start = index;
+        }
+
+        let sentence = text[start..].trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+
+        sentences
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_chinese() {
+        // The default closing characters contain “, which opens a quote in Chinese
+        let rules = Rules {
+            segmenter_closing_characters: vec!['”', '’'],
+            ..Default::default()
+        };
+        let segmenter = SpacelessSegmenter::from_rules(&rules);
+
+        assert_eq!(
+            segmenter.split("我喜欢北京。你呢？“真的吗？！”他问。版本是3.14，见example.com"),
+            vec!["我喜欢北京。", "你呢？", "“真的吗？！”", "他问。", "版本是3.14，见example.com"]
+        );
+        assert_eq!(segmenter.split(""), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_japanese() {
+        let rules = Rules {
+            dictionary_words: ["モーニング娘。", "。"].iter().map(|word| word.to_string()).collect(),
+            ..Default::default()
+        };
+        let segmenter = SpacelessSegmenter::from_rules(&rules);
+
+        assert_eq!(
+            segmenter.split("「モーニング娘。」が好きです。モーニング娘。は歌手です！"),
+            vec!["「モーニング娘。」が好きです。", "モーニング娘。は歌手です！"]
+        );
+    }
+
+    #[test]
+    fn test_khmer() {
+        let rules = Rules {
+            segmenter_terminators: vec!['។', '៕', '?', '!'],
+            ..Default::default()
+        };
+        let segmenter = SpacelessSegmenter::from_rules(&rules);
+
+        assert_eq!(segmenter.split("ខ្ញុំជាសិស្ស។ អ្នកជានរណា?"), vec!["ខ្ញុំជាសិស្ស។", "អ្នកជានរណា?"]);
+    }
+}
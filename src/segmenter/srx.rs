@@ -0,0 +1,282 @@
+use super::Segmenter;
+use crate::rules::{read_rules_file, Rules};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::Reader;
+use regex::Regex;
+use std::collections::HashMap;
+
+// Applies the segmentation rules of an SRX (Segmentation Rules eXchange) file. Every
+// rule consists of a regex before and one after a possible break position and decides
+// whether to break there or not. At every position the first matching rule wins, the
+// text is not split where no rule matches.
+pub struct SrxSegmenter {
+    rules: Vec<SrxRule>,
+}
+
+// The regexes are anchored at the position, so that every rule gets tested at every
+// position, no matter where other matches of it start or end
+struct SrxRule {
+    is_break: bool,
+    before: Regex,
+    after: Regex,
+}
+
+#[derive(Default)]
+struct SrxDocument {
+    cascade: bool,
+    language_rules: HashMap<String, Vec<(bool, String, String)>>,
+    // Pairs of the language pattern and the name of the language rule
+    language_maps: Vec<(String, String)>,
+}
+
+impl SrxSegmenter {
+    pub fn new(rules: &Rules, language: &str, rules_dir: Option<&str>) -> Result<SrxSegmenter, String> {
+        if rules.srx_file.is_empty() {
+            return Err(String::from("srx_file is needed for the srx segmenter"));
+        }
+        let srx_file = read_rules_file(rules_dir, &rules.srx_file)?
+            .ok_or_else(|| format!("SRX file {:?} not found", rules.srx_file))?;
+        eprintln!("Using SRX file = {:?}", srx_file.name);
+
+        SrxSegmenter::parse(&srx_file.content, language).map_err(|e| format!("{}: {}", srx_file.name, e))
+    }
+
+    // Uses the language rules whose language pattern matches the language code. With
+    // cascading all matching language rules are used in order, otherwise only the first.
+    pub fn parse(content: &str, language: &str) -> Result<SrxSegmenter, String> {
+        let document = read_document(content)?;
+        let mut rules = vec![];
+        let mut matched = false;
+        for (pattern, name) in document.language_maps.iter() {
+            let language_pattern = Regex::new(&format!("^(?:{})$", pattern))
+                .map_err(|e| format!("invalid languagepattern {:?}: {}", pattern, e))?;
+            if !language_pattern.is_match(language) {
+                continue;
+            }
+
+            let language_rules = document.language_rules.get(name)
+                .ok_or_else(|| format!("languagerule {:?} not found", name))?;
+            for (is_break, before, after) in language_rules.iter() {
+                let invalid_rule = |e| format!("languagerule {:?}: invalid rule {:?} / {:?}: {}", name, before, after, e);
+                rules.push(SrxRule {
+                    is_break: *is_break,
+                    before: Regex::new(&format!("(?:{})$", before)).map_err(invalid_rule)?,
+                    after: Regex::new(&format!("^(?:{})", after)).map_err(invalid_rule)?,
+                });
+            }
+            matched = true;
+            if !document.cascade {
+                break;
+            }
+        }
+        if !matched {
+            return Err(format!("No languagemap matches the language {:?}", language));
+        }
+
+        Ok(SrxSegmenter { rules })
+    }
+}
+
+impl SrxRule {
+    // The text before the position has to end with a match of the before regex and the
+    // text after it has to start with one of the after regex. Both only see their side
+    // of the text, so a \b right at the position can't look at the other side.
+    fn matches(&self, text: &str, position: usize) -> bool {
+        self.after.is_match(&text[position..]) && self.before.is_match(&text[..position])
+    }
+}
+
+impl Segmenter for SrxSegmenter {
+    fn split(&self, text: &str) -> Vec<String> {
+        let mut sentences = vec![];
+        let mut start = 0;
+        let positions = text.char_indices().skip(1).map(|(position, _)| position);
+        for position in positions {
+            let rule = self.rules.iter().find(|rule| rule.matches(text, position));
+            if !rule.map(|rule| rule.is_break).unwrap_or(false) {
+                continue;
+            }
+
+            let sentence = text[start..position].trim();
+            if !sentence.is_empty() {
+                sentences.push(sentence.to_string());
+            }
+            start = position;
+        }
+
+        let sentence = text[start..].trim();
+        if !sentence.is_empty() {
+            sentences.push(sentence.to_string());
+        }
+
+        sentences
+    }
+}
+
+fn read_document(content: &str) -> Result<SrxDocument, String> {
+    let mut reader = Reader::from_str(content);
+    let mut document = SrxDocument::default();
+    let mut language_rule = String::new();
+    let mut rule = (true, String::new(), String::new());
+    let mut current_tag = Vec::new();
+
+    loop {
+        match reader.read_event().map_err(|e| format!("invalid xml: {}", e))? {
+            Event::Start(element) => {
+                match element.local_name().as_ref() {
+                    b"header" => document.cascade = attribute(&element, "cascade")? == "yes",
+                    b"languagerule" => {
+                        language_rule = attribute(&element, "languagerulename")?;
+                        document.language_rules.entry(language_rule.clone()).or_default();
+                    },
+                    b"rule" => rule = (attribute(&element, "break")? != "no", String::new(), String::new()),
+                    _ => {},
+                }
+                current_tag = element.local_name().as_ref().to_vec();
+            },
+            Event::Empty(element) => {
+                match element.local_name().as_ref() {
+                    b"header" => document.cascade = attribute(&element, "cascade")? == "yes",
+                    b"languagemap" => document.language_maps.push((
+                        attribute(&element, "languagepattern")?,
+                        attribute(&element, "languagerulename")?,
+                    )),
+                    _ => {},
+                }
+            },
+            Event::Text(content) => {
+                let value = content.unescape().map_err(|e| format!("invalid xml: {}", e))?;
+                match current_tag.as_slice() {
+                    b"beforebreak" => rule.1.push_str(&value),
+                    b"afterbreak" => rule.2.push_str(&value),
+                    _ => {},
+                }
+            },
+            Event::End(element) => {
+                if element.local_name().as_ref() == b"rule" {
+                    let rule = std::mem::replace(&mut rule, (true, String::new(), String::new()));
+                    document.language_rules.entry(language_rule.clone()).or_default().push(rule);
+                }
+                current_tag.clear();
+            },
+            Event::Eof => break,
+            _ => {},
+        }
+    }
+
+    Ok(document)
+}
+
+// Missing attributes are returned as empty strings
+fn attribute(element: &BytesStart, name: &str) -> Result<String, String> {
+    let value = element.try_get_attribute(name).map_err(|e| format!("invalid xml: {}", e))?;
+    match value {
+        Some(value) => Ok(value.unescape_value().map_err(|e| format!("invalid xml: {}", e))?.to_string()),
+        None => Ok(String::new()),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::test_helpers::temp_dir_with;
+
+    const SRX: &str = r#"<?xml version="1.0" encoding="UTF-8"?>
+<srx xmlns="http://www.lisa.org/srx20" version="2.0">
+  <header segmentsubflows="yes" cascade="yes"/>
+  <body>
+    <languagerules>
+      <languagerule languagerulename="German">
+        <rule break="no">
+          <beforebreak>\b(?:z\.B|vgl|Dr)\.</beforebreak>
+          <afterbreak>\s</afterbreak>
+        </rule>
+      </languagerule>
+      <languagerule languagerulename="Default">
+        <rule break="no">
+          <beforebreak>\b\p{Lu}\.</beforebreak>
+          <afterbreak>\s</afterbreak>
+        </rule>
+        <rule break="yes">
+          <beforebreak>[.?!]+["”)]*</beforebreak>
+          <afterbreak>\s+["“(]?\p{Lu}</afterbreak>
+        </rule>
+        <rule>
+          <beforebreak>&#10;</beforebreak>
+          <afterbreak></afterbreak>
+        </rule>
+      </languagerule>
+    </languagerules>
+    <maprules>
+      <languagemap languagepattern="[Dd][Ee].*" languagerulename="German"/>
+      <languagemap languagepattern=".*" languagerulename="Default"/>
+    </maprules>
+  </body>
+</srx>
+"#;
+
+    #[test]
+    fn test_split() {
+        let segmenter = SrxSegmenter::parse(SRX, "en").unwrap();
+
+        assert_eq!(
+            segmenter.split("I am a sentence. Me too! \"Really?\" Asked J. S. Bach.\nNew line"),
+            vec!["I am a sentence.", "Me too!", "\"Really?\"", "Asked J. S. Bach.", "New line"]
+        );
+        assert_eq!(segmenter.split("Ends with 3. a lowercase word"), vec!["Ends with 3. a lowercase word"]);
+        assert_eq!(segmenter.split(""), Vec::<String>::new());
+    }
+
+    // Every position a rule allows is a break, even if a longer match of the before
+    // regex overlaps it
+    #[test]
+    fn test_variable_length_beforebreak() {
+        let srx = r#"<srx><body>
+  <languagerules>
+    <languagerule languagerulename="Numbers">
+      <rule break="yes"><beforebreak>\d+</beforebreak><afterbreak>\d</afterbreak></rule>
+    </languagerule>
+  </languagerules>
+  <maprules><languagemap languagepattern=".*" languagerulename="Numbers"/></maprules>
+</body></srx>"#;
+
+        let segmenter = SrxSegmenter::parse(srx, "en").unwrap();
+        assert_eq!(segmenter.split("Call 123 now"), vec!["Call 1", "2", "3 now"]);
+    }
+
+    #[test]
+    fn test_cascade() {
+        let text = "Das ist vgl. Dr. Müller. Neuer Satz.";
+        assert_eq!(SrxSegmenter::parse(SRX, "de").unwrap().split(text), vec!["Das ist vgl. Dr. Müller.", "Neuer Satz."]);
+        assert_eq!(SrxSegmenter::parse(SRX, "en").unwrap().split(text), vec!["Das ist vgl.", "Dr.", "Müller.", "Neuer Satz."]);
+
+        let without_cascade = SRX.replace("cascade=\"yes\"", "cascade=\"no\"");
+        assert_eq!(SrxSegmenter::parse(&without_cascade, "de").unwrap().split(text), vec![text]);
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        let unmapped = SRX.replace("languagepattern=\".*\"", "languagepattern=\"en\"");
+        assert_eq!(SrxSegmenter::parse(&unmapped, "fr").err().unwrap(), "No languagemap matches the language \"fr\"");
+        assert!(SrxSegmenter::parse(&SRX.replace("[.?!]+", "[.?!"), "en").is_err());
+        assert!(SrxSegmenter::parse("<srx><body>", "en").is_err());
+    }
+
+    #[test]
+    fn test_load_file() {
+        let rules_dir = temp_dir_with(&[("srx/xx.srx", SRX)]);
+
+        let rules = Rules {
+            segmenter: String::from("srx"),
+            srx_file: String::from("srx/xx.srx"),
+            ..Default::default()
+        };
+        assert!(SrxSegmenter::new(&rules, "xx", rules_dir.path().to_str()).is_ok());
+
+        let missing = Rules { srx_file: String::from("srx/missing.srx"), ..rules };
+        assert_eq!(
+            SrxSegmenter::new(&missing, "xx", rules_dir.path().to_str()).err().unwrap(),
+            "SRX file \"srx/missing.srx\" not found"
+        );
+    }
+}
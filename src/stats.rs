@@ -0,0 +1,83 @@
+use crate::checker::Rejection;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+
+// Number of offending words/patterns/symbols shown per rule in the run summary.
+// The JSON output always contains all of them.
+const MAX_DETAILS_PRINTED: usize = 5;
+
+// Histogram of the rules that rejected sentences during an extraction.
+#[derive(Debug, Default, Serialize)]
+pub struct RejectionStats {
+    pub rules: HashMap<String, usize>,
+    pub details: HashMap<String, HashMap<String, usize>>,
+}
+
+impl RejectionStats {
+    pub fn record(&mut self, rejection: &Rejection) {
+        let rule = rejection.rule().to_string();
+        *self.rules.entry(rule.clone()).or_insert(0) += 1;
+        if let Some(detail) = rejection.detail() {
+            *self.details.entry(rule).or_default().entry(detail).or_insert(0) += 1;
+        }
+    }
+
+    pub fn total(&self) -> usize {
+        self.rules.values().sum()
+    }
+
+    pub fn print(&self) {
+        eprintln!("rejected sentences = {:?}", self.total());
+        for (rule, count) in sorted_by_count(&self.rules) {
+            eprintln!("  {} = {:?}", rule, count);
+            if let Some(details) = self.details.get(rule) {
+                for (detail, detail_count) in sorted_by_count(details).into_iter().take(MAX_DETAILS_PRINTED) {
+                    eprintln!("    {:?} = {:?}", detail, detail_count);
+                }
+            }
+        }
+    }
+
+    pub fn write_json(&self, path: &str) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(self).map_err(|e| format!("{}", e))?;
+        fs::write(path, json).map_err(|e| format!("{}", e))
+    }
+}
+
+fn sorted_by_count(counts: &HashMap<String, usize>) -> Vec<(&String, &usize)> {
+    let mut sorted = counts.iter().collect::<Vec<_>>();
+    sorted.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    sorted
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_record() {
+        let mut stats = RejectionStats::default();
+        stats.record(&Rejection::MaxWordCount);
+        stats.record(&Rejection::DisallowedWords(String::from("blerg")));
+        stats.record(&Rejection::DisallowedWords(String::from("blerg")));
+        stats.record(&Rejection::DisallowedWords(String::from("foo")));
+
+        assert_eq!(stats.total(), 4);
+        assert_eq!(stats.rules["max_word_count"], 1);
+        assert_eq!(stats.rules["disallowed_words"], 3);
+        assert_eq!(stats.details["disallowed_words"]["blerg"], 2);
+        assert_eq!(stats.details.get("max_word_count"), None);
+    }
+
+    #[test]
+    fn test_sorted_by_count() {
+        let mut counts = HashMap::new();
+        counts.insert(String::from("b"), 1);
+        counts.insert(String::from("c"), 5);
+        counts.insert(String::from("a"), 1);
+
+        let sorted = sorted_by_count(&counts).into_iter().map(|(key, _)| key.as_str()).collect::<Vec<_>>();
+        assert_eq!(sorted, vec!["c", "a", "b"]);
+    }
+}
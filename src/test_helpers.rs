@@ -0,0 +1,15 @@
+use std::fs;
+use tempfile::TempDir;
+
+// Creates a temporary directory with the given files, which is removed again when
+// the returned directory is dropped. File names can include subdirectories.
+pub fn temp_dir_with(files: &[(&str, &str)]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for (file_name, content) in files {
+        let path = dir.path().join(file_name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, content).unwrap();
+    }
+
+    dir
+}
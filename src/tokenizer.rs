@@ -0,0 +1,169 @@
+use std::collections::HashSet;
+
+// Punctuation of scripts written without spaces, which never belongs to a word
+const PUNCTUATION: [char; 36] = [
+    '。', '、', '，', '．', '！', '？', '；', '：', '・', '…', '‥', '「', '」', '『', '』', '（', '）', '〔',
+    '〕', '［', '］', '〈', '〉', '《', '》', '【', '】', '“', '”', '‘', '’', '«', '»', '—', '។', '៕',
+];
+
+// Splits texts of scripts without spaces between words, such as Thai or Chinese, into
+// words using a word list. At every position the longest word of the list is taken.
+// Characters not starting any known word are grouped into a word of their own until
+// the next known word, space or punctuation.
+#[derive(Debug)]
+pub struct DictionaryTokenizer {
+    // Lowercase words
+    words: HashSet<String>,
+    // Length of the longest word in characters
+    max_length: usize,
+}
+
+impl DictionaryTokenizer {
+    pub fn new<'a>(words: impl IntoIterator<Item = &'a String>) -> DictionaryTokenizer {
+        let words = words.into_iter()
+            .map(|word| lowercase(word.trim()))
+            .filter(|word| !word.is_empty())
+            .collect::<HashSet<_>>();
+        let max_length = words.iter().map(|word| word.chars().count()).max().unwrap_or_default();
+
+        DictionaryTokenizer { words, max_length }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.words.is_empty()
+    }
+
+    pub fn tokenize<'a>(&self, text: &'a str) -> Vec<&'a str> {
+        let lowercase_text = LowercaseText::new(text);
+        let mut words = vec![];
+        let mut unknown_start = None;
+        let mut index = 0;
+        while let Some(c) = text[index..].chars().next() {
+            let known_word = self.longest_match(&lowercase_text, index)
+                .filter(|length| !splits_latin_word(text, index) && !splits_latin_word(text, index + length));
+            if let Some(length) = known_word {
+                if let Some(start) = unknown_start.take() {
+                    words.push(&text[start..index]);
+                }
+                words.push(&text[index..index + length]);
+                index += length;
+                continue;
+            }
+
+            if is_separator(c) {
+                if let Some(start) = unknown_start.take() {
+                    words.push(&text[start..index]);
+                }
+            } else if unknown_start.is_none() {
+                unknown_start = Some(index);
+            }
+            index += c.len_utf8();
+        }
+        if let Some(start) = unknown_start {
+            words.push(&text[start..]);
+        }
+
+        words
+    }
+
+    // Length in bytes of the longest known word starting at the byte index of the text
+    pub fn longest_match(&self, text: &LowercaseText, index: usize) -> Option<usize> {
+        let start = text.offsets[index];
+        text.text[index..].char_indices()
+            .skip(1)
+            .map(|(length, _)| index + length)
+            .chain(std::iter::once(text.text.len()))
+            .take(self.max_length)
+            .filter(|end| self.words.contains(&text.lowercase[start..text.offsets[*end]]))
+            .last()
+            .map(|end| end - index)
+    }
+}
+
+// A text together with its lowercase version, which is created once so that words can
+// be looked up at every position without allocating
+pub struct LowercaseText<'a> {
+    text: &'a str,
+    lowercase: String,
+    // Byte index in the lowercase version for every char boundary of the text
+    offsets: Vec<usize>,
+}
+
+impl<'a> LowercaseText<'a> {
+    pub fn new(text: &'a str) -> LowercaseText<'a> {
+        let mut lowercase = String::with_capacity(text.len());
+        let mut offsets = vec![0; text.len() + 1];
+        for (index, c) in text.char_indices() {
+            offsets[index] = lowercase.len();
+            lowercase.extend(c.to_lowercase());
+        }
+        offsets[text.len()] = lowercase.len();
+
+        LowercaseText { text, lowercase, offsets }
+    }
+}
+
+// Lowercases char by char, as the chars of a text are mapped to their lowercase
+// version one by one as well
+fn lowercase(text: &str) -> String {
+    text.chars().flat_map(char::to_lowercase).collect()
+}
+
+// Known words must not start or end in the middle of a word in Latin script
+fn splits_latin_word(text: &str, index: usize) -> bool {
+    text[..index].ends_with(|c: char| c.is_ascii_alphanumeric())
+        && text[index..].starts_with(|c: char| c.is_ascii_alphanumeric())
+}
+
+pub fn is_separator(c: char) -> bool {
+    c.is_whitespace() || c.is_ascii_punctuation() || PUNCTUATION.contains(&c)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn tokenizer(words: &[&str]) -> DictionaryTokenizer {
+        let words = words.iter().map(|word| word.to_string()).collect::<Vec<_>>();
+        DictionaryTokenizer::new(words.iter())
+    }
+
+    #[test]
+    fn test_tokenize_chinese() {
+        let tokenizer = tokenizer(&["我", "喜欢", "北京", "北京大学", "大学"]);
+
+        assert_eq!(tokenizer.tokenize("我喜欢北京大学。"), vec!["我", "喜欢", "北京大学"]);
+        assert_eq!(tokenizer.tokenize("我喜欢Rust和北京"), vec!["我", "喜欢", "Rust和", "北京"]);
+        assert_eq!(tokenizer.tokenize("「北京」，大学"), vec!["北京", "大学"]);
+    }
+
+    #[test]
+    fn test_tokenize_thai() {
+        let tokenizer = tokenizer(&["ฉัน", "ไม่", "ชอบ", "กิน", "ข้าว"]);
+
+        assert_eq!(tokenizer.tokenize("ฉันไม่ชอบกินข้าว"), vec!["ฉัน", "ไม่", "ชอบ", "กิน", "ข้าว"]);
+        assert_eq!(tokenizer.tokenize("ฉันชอบ กินผัดไทย"), vec!["ฉัน", "ชอบ", "กิน", "ผัดไทย"]);
+    }
+
+    #[test]
+    fn test_tokenize_latin() {
+        let tokenizer = tokenizer(&["a", "Cat"]);
+
+        assert_eq!(tokenizer.tokenize("a cat, a catalog."), vec!["a", "cat", "a", "catalog"]);
+        assert_eq!(tokenizer.tokenize("bat"), vec!["bat"]);
+        assert_eq!(tokenizer.tokenize(""), Vec::<&str>::new());
+    }
+
+    #[test]
+    fn test_longest_match() {
+        let words = tokenizer(&["モーニング娘。", "モー", "ab"]);
+        let longest_match = |text| words.longest_match(&LowercaseText::new(text), 0);
+
+        assert_eq!(longest_match("モーニング娘。です"), Some("モーニング娘。".len()));
+        assert_eq!(longest_match("モーター"), Some("モー".len()));
+        assert_eq!(longest_match("です"), None);
+        // The lowercase version of İ is longer than the letter itself
+        assert_eq!(words.longest_match(&LowercaseText::new("İAB"), "İ".len()), Some(2));
+        assert!(tokenizer(&[" "]).is_empty());
+    }
+}
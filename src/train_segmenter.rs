@@ -0,0 +1,67 @@
+use crate::extractor::load_file_names;
+use crate::loaders::Loader;
+use crate::replacer::replace_strings;
+use crate::rules::load_rules;
+use crate::segmenter::{model_file_name, PunktModel};
+use std::collections::HashSet;
+use std::fs;
+use std::path::Path;
+
+pub struct TrainOptions {
+    // The model gets written to the rules directory, where the extraction finds it
+    pub rules_dir: String,
+    // Training stops reading texts once it has this many characters
+    pub max_characters: usize,
+}
+
+pub fn train_segmenter(loader: impl Loader, options: TrainOptions) -> Result<(), String> {
+    let config = loader.get_config();
+    let rules = load_rules(&config.language, Some(&options.rules_dir))?;
+    if !rules.segmenter.is_empty() && rules.segmenter != "punkt" {
+        eprintln!("Warning: the rules use the {:?} segmenter, the model is only used by punkt", rules.segmenter);
+    }
+
+    // The segmenter gets the texts after the replacements during an extraction
+    let mut corpus = String::new();
+    let mut characters = 0;
+    for file_name in load_file_names(&config.directory, &config.file_prefix)? {
+        if characters >= options.max_characters {
+            break;
+        }
+        eprintln!("file_name = {:?}", file_name.to_string_lossy());
+        loader.load_chunks(&file_name, &HashSet::new(), &mut |texts| {
+            for text in texts {
+                if characters >= options.max_characters {
+                    break;
+                }
+                let replaced = replace_strings(&rules, &text.content);
+                characters += replaced.chars().count();
+                corpus.push_str(&replaced);
+                corpus.push_str("\n\n");
+            }
+            Ok(())
+        })?;
+    }
+    if corpus.trim().is_empty() {
+        return Err(format!("No texts found in {:?} to train the segmenter on", config.directory));
+    }
+
+    eprintln!("Training on {} characters", characters);
+    let model = PunktModel::train(&corpus);
+    eprintln!(
+        "Learned {} abbreviations, {} collocations and {} sentence starters",
+        model.abbreviations.len(),
+        model.collocations.len(),
+        model.sentence_starters.len(),
+    );
+
+    let path = Path::new(&options.rules_dir).join(model_file_name(&config.language));
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent).map_err(|e| format!("{}", e))?;
+    }
+    let json = serde_json::to_string_pretty(&model).map_err(|e| format!("{}", e))?;
+    fs::write(&path, json + "\n").map_err(|e| format!("{:?}: {}", path, e))?;
+    eprintln!("Wrote punkt model to {:?}", path);
+
+    Ok(())
+}
@@ -0,0 +1,341 @@
+use crate::rules::{read_language_rules_file, resolve_rules, Rules, INHERITANCE_KEYS};
+use crate::segmenter::SEGMENTERS;
+use regex::Regex;
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::fmt;
+use toml::{Spanned, Value};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+// A problem found in a rules file, with the line it was found on
+#[derive(Debug, PartialEq, Eq)]
+pub struct Issue {
+    pub severity: Severity,
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for Issue {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let severity = match self.severity {
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        };
+        write!(f, "line {}: {}: {}", self.line, severity, self.message)
+    }
+}
+
+// The array entries of the rules file which need further checks, with their position
+// in the file. Only used to report the exact line of an invalid entry.
+#[derive(Default, Deserialize)]
+#[serde(default)]
+struct ArrayEntries {
+    remove_brackets_list: Vec<Spanned<(String, String)>>,
+    disallowed_symbols: Vec<Spanned<String>>,
+    abbreviation_patterns: Vec<Spanned<String>>,
+    other_patterns: Vec<Spanned<String>>,
+    append: Option<Box<ArrayEntries>>,
+}
+
+struct Validator<'a> {
+    content: &'a str,
+    keys: BTreeMap<Spanned<String>, Spanned<Value>>,
+    issues: Vec<Issue>,
+}
+
+impl<'a> Validator<'a> {
+    fn line(&self, offset: usize) -> usize {
+        self.content[..offset.min(self.content.len())].matches('\n').count() + 1
+    }
+
+    fn key_line(&self, key: &str) -> usize {
+        self.keys.keys()
+            .find(|spanned| spanned.get_ref() == key)
+            .map(|spanned| self.line(spanned.span().start))
+            .unwrap_or(1)
+    }
+
+    fn push(&mut self, severity: Severity, line: usize, message: String) {
+        self.issues.push(Issue { severity, line, message });
+    }
+
+    fn check_unknown_keys(&mut self) {
+        let known_keys = match serde_json::to_value(Rules::default()) {
+            Ok(serde_json::Value::Object(fields)) => fields,
+            _ => return,
+        };
+        let unknown = self.keys.keys()
+            .filter(|key| !known_keys.contains_key(key.get_ref()) && !INHERITANCE_KEYS.contains(&key.get_ref().as_str()))
+            .map(|key| (self.line(key.span().start), key.get_ref().clone()))
+            .collect::<Vec<_>>();
+        for (line, key) in unknown {
+            self.push(Severity::Warning, line, format!("unknown key {:?} is ignored", key));
+        }
+    }
+
+    fn check_regex(&mut self, name: &str, line: usize, pattern: &str) {
+        if let Err(e) = Regex::new(pattern) {
+            let message = format!("{}: invalid regex {:?}: {}", name, pattern, e);
+            self.push(Severity::Error, line, message);
+        }
+    }
+
+    fn check_entries(&mut self, entries: &ArrayEntries) {
+        for entry in entries.remove_brackets_list.iter() {
+            let (open, close) = entry.get_ref();
+            let pattern = format!(r#"\{}[^\{}\{}]*\{}"#, open, open, close, close);
+            self.check_regex("remove_brackets_list", self.line(entry.span().start), &pattern);
+        }
+
+        for entry in entries.disallowed_symbols.iter() {
+            if entry.get_ref().chars().count() != 1 {
+                let message = format!("disallowed_symbols: {:?} is not a single symbol and never matches", entry.get_ref());
+                self.push(Severity::Warning, self.line(entry.span().start), message);
+            }
+        }
+
+        let patterns = [
+            ("abbreviation_patterns", &entries.abbreviation_patterns),
+            ("other_patterns", &entries.other_patterns),
+        ];
+        for (name, patterns) in patterns {
+            for pattern in patterns {
+                self.check_regex(name, self.line(pattern.span().start), pattern.get_ref());
+            }
+        }
+
+        if let Some(append) = &entries.append {
+            self.check_entries(append);
+        }
+    }
+
+    fn check_settings(&mut self, rules: &Rules) {
+        if !rules.allowed_symbols_regex.is_empty() {
+            let line = self.key_line("allowed_symbols_regex");
+            self.check_regex("allowed_symbols_regex", line, &rules.allowed_symbols_regex);
+            if !rules.disallowed_symbols.is_empty() {
+                let line = self.key_line("disallowed_symbols");
+                let message = String::from("disallowed_symbols is ignored as allowed_symbols_regex is set");
+                self.push(Severity::Warning, line, message);
+            }
+        }
+        if !rules.stem_separator_regex.is_empty() {
+            let line = self.key_line("stem_separator_regex");
+            self.check_regex("stem_separator_regex", line, &rules.stem_separator_regex);
+        }
+        if !SEGMENTERS.contains(&rules.segmenter.as_str()) {
+            let line = self.key_line("segmenter");
+            let message = format!("segmenter {:?} is not supported", rules.segmenter);
+            self.push(Severity::Error, line, message);
+        }
+        if !SEGMENTERS.contains(&rules.segmenter_fallback.as_str()) {
+            let line = self.key_line("segmenter_fallback");
+            let message = format!("segmenter_fallback {:?} is not supported", rules.segmenter_fallback);
+            self.push(Severity::Error, line, message);
+        }
+        if rules.segmenter == "rules" && rules.segmenter_terminators.is_empty() {
+            let line = self.key_line("segmenter_terminators");
+            let message = String::from("segmenter_terminators is empty, texts never get split into sentences");
+            self.push(Severity::Error, line, message);
+        }
+        if rules.segmenter == "srx" && rules.srx_file.is_empty() {
+            let line = self.key_line("segmenter");
+            let message = String::from("srx_file is needed for the srx segmenter");
+            self.push(Severity::Error, line, message);
+        }
+        if !["", "dictionary"].contains(&rules.word_tokenizer.as_str()) {
+            let line = self.key_line("word_tokenizer");
+            let message = format!("word_tokenizer {:?} is not supported", rules.word_tokenizer);
+            self.push(Severity::Error, line, message);
+        }
+        if rules.min_word_count > rules.max_word_count {
+            let line = self.key_line("min_word_count");
+            let message = String::from("min_word_count is larger than max_word_count, no sentence can pass");
+            self.push(Severity::Error, line, message);
+        }
+        if rules.min_characters > rules.max_characters {
+            let line = self.key_line("min_characters");
+            let message = String::from("min_characters is larger than max_characters, no sentence can pass");
+            self.push(Severity::Error, line, message);
+        }
+        if !(0.0..=1.0).contains(&rules.near_duplicate_threshold) {
+            let line = self.key_line("near_duplicate_threshold");
+            let message = String::from("near_duplicate_threshold needs to be between 0 and 1");
+            self.push(Severity::Error, line, message);
+        }
+    }
+}
+
+// Checks the content of a rules file. Syntax and type errors are reported on their
+// own, as the file can't be checked any further in that case. Files extending other
+// rules only contain some of the rules, so their settings are checked once resolved.
+pub fn validate_rules_str(content: &str) -> Vec<Issue> {
+    let parsed = toml::from_str::<Rules>(content).and_then(|rules| {
+        let keys = toml::from_str::<BTreeMap<Spanned<String>, Spanned<Value>>>(content)?;
+        let entries = toml::from_str::<ArrayEntries>(content)?;
+        Ok((rules, keys, entries))
+    });
+    let (rules, keys, entries) = match parsed {
+        Ok(parsed) => parsed,
+        Err(e) => {
+            let line = e.span().map(|span| content[..span.start].matches('\n').count() + 1).unwrap_or(1);
+            return vec![Issue { severity: Severity::Error, line, message: e.message().to_string() }];
+        },
+    };
+
+    let extends = keys.keys().any(|key| key.get_ref() == "extends");
+    let mut validator = Validator { content, keys, issues: vec![] };
+    validator.check_unknown_keys();
+    validator.check_entries(&entries);
+    if !extends {
+        validator.check_settings(&rules);
+    }
+    validator.issues.sort_by_key(|issue| issue.line);
+    validator.issues
+}
+
+// Checks the settings of the rules including everything they inherit
+fn validate_resolved_rules(content: &str, rules: &Rules) -> Vec<Issue> {
+    let keys = toml::from_str(content).unwrap_or_default();
+    let mut validator = Validator { content, keys, issues: vec![] };
+    validator.check_settings(rules);
+    validator.issues
+}
+
+pub fn validate_rules(language: &str, rules_dir: Option<&str>) -> Result<(), String> {
+    let rules_file = read_language_rules_file(rules_dir, language)?;
+    let file_name = rules_file.name;
+    let mut issues = validate_rules_str(&rules_file.content);
+    let has_errors = issues.iter().any(|issue| issue.severity == Severity::Error);
+    let extends = toml::from_str::<toml::Table>(&rules_file.content)
+        .map(|table| table.contains_key("extends"))
+        .unwrap_or(false);
+    if !has_errors && extends {
+        match resolve_rules(language, rules_dir) {
+            Ok(rules) => issues.extend(validate_resolved_rules(&rules_file.content, &rules)),
+            Err(message) => issues.push(Issue { severity: Severity::Error, line: 1, message }),
+        }
+    }
+
+    for issue in issues.iter() {
+        eprintln!("{}: {}", file_name, issue);
+    }
+
+    let errors = issues.iter().filter(|issue| issue.severity == Severity::Error).count();
+    if errors > 0 {
+        return Err(format!("{} has {} error(s)", file_name, errors));
+    }
+
+    eprintln!("{} is valid", file_name);
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn messages(content: &str) -> Vec<String> {
+        validate_rules_str(content).iter().map(|issue| issue.to_string()).collect()
+    }
+
+    #[test]
+    fn test_valid_rules() {
+        assert_eq!(messages("min_word_count = 2\nreplacements = [\n  [\"Mr.\", \"Mister\"],\n]\n"), Vec::<String>::new());
+    }
+
+    #[test]
+    fn test_syntax_error() {
+        assert_eq!(validate_rules_str("min_word_count = 2\nmax_word_count = \n").first().unwrap().line, 2);
+    }
+
+    #[test]
+    fn test_wrong_type() {
+        let issues = validate_rules_str("\nmin_word_count = \"two\"\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+    }
+
+    #[test]
+    fn test_invalid_regex() {
+        assert_eq!(
+            validate_rules_str("other_patterns = [\n  \"[a-z]\",\n  \"(unclosed\",\n]\n")[0].line,
+            3
+        );
+    }
+
+    #[test]
+    fn test_invalid_pairs() {
+        let issues = validate_rules_str("replacements = [\n  [\"a\", \"b\"],\n  [\"c\"],\n]\n");
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].severity, Severity::Error);
+        assert_eq!(issues[0].line, 3);
+    }
+
+    #[test]
+    fn test_unknown_key() {
+        assert_eq!(
+            messages("min_word_count = 2\nmax_wordcount = 3\n"),
+            vec!["line 2: warning: unknown key \"max_wordcount\" is ignored"]
+        );
+    }
+
+    #[test]
+    fn test_conflicting_settings() {
+        let issues = validate_rules_str("allowed_symbols_regex = \"[a-z]\"\ndisallowed_symbols = [\"%\"]\nmin_word_count = 5\nmax_word_count = 3\n");
+        assert_eq!(issues.len(), 2);
+        assert_eq!(issues[0].severity, Severity::Warning);
+        assert_eq!(issues[0].line, 2);
+        assert_eq!(issues[1].severity, Severity::Error);
+        assert_eq!(issues[1].line, 3);
+    }
+
+    #[test]
+    fn test_rules_segmenter_without_terminators() {
+        assert_eq!(
+            messages("segmenter = \"rules\"\nsegmenter_terminators = []\n"),
+            vec!["line 2: error: segmenter_terminators is empty, texts never get split into sentences"]
+        );
+    }
+
+    #[test]
+    fn test_unknown_segmenter_fallback() {
+        assert_eq!(
+            messages("segmenter = \"python\"\nsegmenter_fallback = \"unknown\"\n"),
+            vec!["line 2: error: segmenter_fallback \"unknown\" is not supported"]
+        );
+    }
+
+    #[test]
+    fn test_srx_segmenter_without_file() {
+        assert_eq!(messages("segmenter = \"srx\"\n"), vec!["line 1: error: srx_file is needed for the srx segmenter"]);
+    }
+
+    #[test]
+    fn test_unknown_word_tokenizer() {
+        assert_eq!(messages("word_tokenizer = \"jieba\"\n"), vec!["line 1: error: word_tokenizer \"jieba\" is not supported"]);
+    }
+
+    #[test]
+    fn test_extends() {
+        assert_eq!(messages("extends = \"pt\"\nmin_word_count = 20\n\n[append]\nother_patterns = [\"(\"]\n"), vec![
+            "line 5: error: other_patterns: invalid regex \"(\": regex parse error:\n    (\n    ^\nerror: unclosed group",
+        ]);
+    }
+
+    #[test]
+    fn test_resolved_rules() {
+        let rules = Rules {
+            min_word_count: 20,
+            ..Default::default()
+        };
+
+        let issues = validate_resolved_rules("extends = \"pt\"\nmin_word_count = 20\n", &rules);
+        assert_eq!(issues.len(), 1);
+        assert_eq!(issues[0].line, 2);
+    }
+}